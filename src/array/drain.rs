@@ -1,42 +1,135 @@
-use super::Array;
-use std::{alloc::Allocator, ops::RangeBounds};
+use super::{Array, DrainError, range_to_bounds};
+use std::{
+    alloc::Allocator,
+    fmt::Debug,
+    iter::FusedIterator,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::RangeBounds,
+};
 
 impl<T, A: Allocator> Array<T, A> {
+    /// Removes the elements in `range` from the array, returning them as an iterator.
+    /// The elements after the range are shifted back once the iterator is dropped.
+    /// # Panics
+    /// Panics if the range is out of bounds, see [Self::try_drain] for a non-panicking version.
+    #[track_caller]
     pub fn drain<'d>(&'d mut self, range: impl RangeBounds<usize>) -> Drain<'d, T, A> {
-        use std::ops::Bound;
-        let start = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(&n) => n,
-            Bound::Excluded(&n) => n.saturating_add(1),
-        };
-        let end = match range.end_bound() {
-            Bound::Unbounded => self.len(),
-            Bound::Included(&n) => n.saturating_add(1),
-            Bound::Excluded(&n) => n,
-        };
-        if start >= self.len() {
-            panic!("Start index {start} would be out of bounds for Array of length {len}", len = self.len());
+        match self.try_drain(range) {
+            Ok(drain) => drain,
+            Err(err) => panic!("{err}"),
+        }
+    }
+    /// Removes the elements in `range` from the array, returning them as an iterator.
+    /// Returns an error if `range` starts past its end or past the end of the array, or ends
+    /// past the end of the array.
+    pub fn try_drain<'d>(
+        &'d mut self,
+        range: impl RangeBounds<usize>,
+    ) -> Result<Drain<'d, T, A>, DrainError> {
+        let len = self.len();
+        let (start, end) = range_to_bounds(range, len);
+        // `len..` is a valid empty range, so only a start past the end is out of bounds.
+        if start > len {
+            return Err(DrainError::StartOutOfBounds { start, len });
+        }
+        if end > len {
+            return Err(DrainError::EndOutOfBounds { end, len });
         }
-        if end > self.len() {
-            panic!("End index {end} would be out of bounds for Array of length {len}", len = self.len());
+        if start > end {
+            return Err(DrainError::StartPastEnd { start, end, len });
         }
         unsafe { self.set_len(start) };
-        Drain {
+        Ok(Drain {
             arr: self,
+            full_len: len,
             hole_start: start,
             hole_end: end,
             start,
             end,
-        }
+        })
     }
 }
 
 pub struct Drain<'d, T, A: Allocator> {
-    arr: &'d mut Array<T, A>,
-    hole_start: usize,
-    hole_end: usize,
-    start: usize,
-    end: usize,
+    pub(super) arr: &'d mut Array<T, A>,
+    /// The length of the array before draining started.
+    pub(super) full_len: usize,
+    pub(super) hole_start: usize,
+    pub(super) hole_end: usize,
+    pub(super) start: usize,
+    pub(super) end: usize,
+}
+
+impl<'d, T, A: Allocator> Drain<'d, T, A> {
+    /// Returns the elements that have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.arr.idx_to_ptr(self.start), self.len()) }
+    }
+    /// Returns a reference to the allocator of the array being drained.
+    pub fn allocator(&self) -> &A {
+        self.arr.allocator()
+    }
+    /// Stops draining, keeping the elements that have not been yielded yet in the array.
+    pub fn keep_rest(self) {
+        // Skip Drop, which would drop the remaining elements.
+        let mut this = ManuallyDrop::new(self);
+        let kept = this.len();
+        let tail = this.full_len - this.hole_end;
+        let new_len = this.hole_start + kept + tail;
+        unsafe {
+            let hole = this.arr.idx_to_ptr(this.hole_start);
+            this.arr.idx_to_ptr(this.start).copy_to(hole, kept);
+            this.arr
+                .idx_to_ptr(this.hole_end)
+                .copy_to(hole.add(kept), tail);
+            this.arr.set_len(new_len);
+        }
+    }
+}
+
+impl<'d, T, A: Allocator + Clone> Drain<'d, T, A> {
+    /// Stops draining, moving the elements that have not been yielded yet into a new array in a
+    /// clone of the allocator.
+    /// The elements after the drained range are shifted back as usual.
+    pub fn into_remaining_array(mut self) -> Array<T, A> {
+        self.take_remaining()
+    }
+    /// Yields the next `N` elements at once.
+    /// If fewer than `N` elements are left, they are all returned in a new array in a clone of the
+    /// allocator instead.
+    pub fn next_array<const N: usize>(&mut self) -> Result<[T; N], Array<T, A>> {
+        if self.len() < N {
+            return Err(self.take_remaining());
+        }
+        let mut out = MaybeUninit::<[T; N]>::uninit();
+        unsafe {
+            self.arr
+                .idx_to_ptr(self.start)
+                .copy_to_nonoverlapping(out.as_mut_ptr().cast(), N);
+            self.start += N;
+            Ok(out.assume_init())
+        }
+    }
+    /// Moves the elements that have not been yielded yet into a new array.
+    fn take_remaining(&mut self) -> Array<T, A> {
+        let remaining = self.len();
+        let mut rest = Array::with_capacity_in(remaining, self.arr.alloc.clone());
+        unsafe {
+            self.arr
+                .idx_to_ptr(self.start)
+                .copy_to_nonoverlapping(rest.idx_to_ptr(0), remaining);
+            rest.set_len(remaining);
+        }
+        // The moved elements must not be dropped by Drop.
+        self.start = self.end;
+        rest
+    }
+}
+
+impl<'d, T: Debug, A: Allocator> Debug for Drain<'d, T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
 }
 
 impl<'d, T, A: Allocator> Iterator for Drain<'d, T, A> {
@@ -50,14 +143,20 @@ impl<'d, T, A: Allocator> Iterator for Drain<'d, T, A> {
         Some(val)
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        for i in (self.start..self.end).take(n) {
-            self.start += 1;
-            unsafe {
-                self.arr.idx_to_ptr(i).drop_in_place();
-            }
+        let skip = n.min(self.len());
+        let first = self.start;
+        // Advance first, drop_in_place on a slice keeps dropping the remaining elements if one of
+        // them panics.
+        self.start += skip;
+        unsafe {
+            core::ptr::slice_from_raw_parts_mut(self.arr.idx_to_ptr(first), skip).drop_in_place();
         }
         self.next()
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
 }
 
 impl<'d, T, A: Allocator> DoubleEndedIterator for Drain<'d, T, A> {
@@ -65,16 +164,16 @@ impl<'d, T, A: Allocator> DoubleEndedIterator for Drain<'d, T, A> {
         if self.start >= self.end {
             return None;
         }
-        self.end += 1;
+        self.end -= 1;
         let val = unsafe { self.arr.idx_to_ptr(self.end).read() };
         Some(val)
     }
     fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        for i in (self.start..self.end).rev().take(n) {
-            self.end -= 1;
-            unsafe {
-                self.arr.idx_to_ptr(i).drop_in_place();
-            }
+        let skip = n.min(self.len());
+        self.end -= skip;
+        unsafe {
+            core::ptr::slice_from_raw_parts_mut(self.arr.idx_to_ptr(self.end), skip)
+                .drop_in_place();
         }
         self.next_back()
     }
@@ -86,20 +185,195 @@ impl<'d, T, A: Allocator> ExactSizeIterator for Drain<'d, T, A> {
     }
 }
 
+impl<'d, T, A: Allocator> FusedIterator for Drain<'d, T, A> {}
+
 impl<'d, T, A: Allocator> Drop for Drain<'d, T, A> {
     fn drop(&mut self) {
         // Need to Drop start..end
-        let full_len = self.arr.len();
+        let full_len = self.full_len;
+        // The array's length marks the start of the hole, past any elements written into it by
+        // Splice.
+        let hole_start = self.arr.len();
         unsafe {
-            self.arr.set_len(self.hole_start);
             for i in self.start..self.end {
                 self.arr.idx_to_ptr(i).drop_in_place();
             }
             let from = self.arr.idx_to_ptr(self.hole_end);
-            let to = self.arr.idx_to_ptr(self.hole_start);
+            let to = self.arr.idx_to_ptr(hole_start);
             let count = full_len - self.hole_end;
             from.copy_to(to, count);
-            self.arr.set_len(self.hole_start + count);
+            self.arr.set_len(hole_start + count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        DrainError,
+        tests::{strings, tracked, values},
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn try_drain_start_out_of_bounds() {
+        let mut arr = strings(0..4);
+        let err = arr.try_drain(5..).err();
+        assert_eq!(err, Some(DrainError::StartOutOfBounds { start: 5, len: 4 }));
+        assert_eq!(arr.len(), 4);
+    }
+
+    #[test]
+    fn try_drain_end_out_of_bounds() {
+        let mut arr = strings(0..4);
+        let err = arr.try_drain(1..=4).err();
+        assert_eq!(err, Some(DrainError::EndOutOfBounds { end: 5, len: 4 }));
+        assert_eq!(arr.len(), 4);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn try_drain_start_past_end() {
+        let mut arr = strings(0..4);
+        let err = arr.try_drain(3..1).err();
+        let expected = DrainError::StartPastEnd {
+            start: 3,
+            end: 1,
+            len: 4,
+        };
+        assert_eq!(err, Some(expected));
+        assert_eq!(arr.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Start index 3 would be past end index 1 for Array of length 4")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn drain_start_past_end() {
+        strings(0..4).drain(3..1);
+    }
+
+    #[test]
+    fn drain_empty_range_at_len() {
+        let mut arr = strings(0..4);
+        assert_eq!(arr.drain(4..).count(), 0);
+        assert_eq!(arr.drain(4..4).count(), 0);
+        assert_eq!(arr.try_drain(4..).map(|drain| drain.len()), Ok(0));
+        assert_eq!(arr[..], ["0", "1", "2", "3"]);
+    }
+
+    #[test]
+    fn keep_rest() {
+        let mut arr = strings(0..10);
+        let mut drain = arr.drain(2..8);
+        assert_eq!(drain.next().as_deref(), Some("2"));
+        assert_eq!(drain.next_back().as_deref(), Some("7"));
+        drain.keep_rest();
+        assert_eq!(arr[..], ["0", "1", "3", "4", "5", "6", "8", "9"]);
+    }
+
+    #[test]
+    fn into_remaining_array() {
+        let mut arr = strings(0..10);
+        let mut drain = arr.drain(2..8);
+        drain.next();
+        let rest = drain.into_remaining_array();
+        assert_eq!(rest[..], ["3", "4", "5", "6", "7"]);
+        assert_eq!(arr[..], ["0", "1", "8", "9"]);
+    }
+
+    #[test]
+    fn next_array_exact_multiple() {
+        let mut arr = strings(0..8);
+        let mut drain = arr.drain(1..7);
+        assert_eq!(drain.next_array::<3>().unwrap(), ["1", "2", "3"]);
+        assert_eq!(drain.next_array::<3>().unwrap(), ["4", "5", "6"]);
+        assert!(drain.next_array::<3>().unwrap_err().is_empty());
+        drop(drain);
+        assert_eq!(arr[..], ["0", "7"]);
+    }
+
+    #[test]
+    fn next_array_trailing_partial() {
+        let mut arr = strings(0..8);
+        let mut drain = arr.drain(..);
+        assert!(drain.next_array::<3>().is_ok());
+        assert!(drain.next_array::<3>().is_ok());
+        let rest = drain.next_array::<3>().unwrap_err();
+        assert_eq!(rest[..], ["6", "7"]);
+        assert_eq!(drain.next(), None);
+        drop(drain);
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn nth_past_end_drops_once() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..8, &drops);
+        let mut drain = arr.drain(1..6);
+        assert!(drain.nth(100).is_none());
+        assert_eq!(drops.get(), 5);
+        assert!(drain.next().is_none());
+        drop(drain);
+        assert_eq!(drops.get(), 5);
+        assert_eq!(values(&arr), [0, 6, 7]);
+    }
+
+    #[test]
+    fn drain_empty_ranges() {
+        let mut arr = strings(0..6);
+        assert_eq!(arr.drain(0..0).count(), 0);
+        assert_eq!(arr.drain(3..3).count(), 0);
+        assert_eq!(arr, strings(0..6));
+    }
+
+    #[test]
+    fn drain_last_element() {
+        let mut arr = strings(0..6);
+        let drained: Vec<_> = arr.drain(5..).collect();
+        assert_eq!(drained, ["5"]);
+        assert_eq!(arr, strings(0..5));
+    }
+
+    #[test]
+    fn size_hint_matches_yielded() {
+        let mut arr = strings(0..10);
+        let mut drain = arr.drain(2..9);
+        let mut yielded = 0;
+        loop {
+            let (lower, upper) = drain.size_hint();
+            assert_eq!(upper, Some(lower));
+            assert_eq!(lower, 7 - yielded);
+            let next = if yielded % 2 == 0 {
+                drain.next()
+            } else {
+                drain.next_back()
+            };
+            if next.is_none() {
+                break;
+            }
+            yielded += 1;
+        }
+        assert_eq!(yielded, 7);
+    }
+
+    #[test]
+    fn fused_after_exhaustion() {
+        let mut arr = strings(0..4);
+        let mut drain = arr.drain(1..3);
+        assert_eq!(drain.by_ref().count(), 2);
+        for _ in 0..3 {
+            assert_eq!(drain.next(), None);
+            assert_eq!(drain.next_back(), None);
         }
     }
+
+    #[test]
+    fn as_slice_shows_unyielded() {
+        let mut arr = strings(0..10);
+        let mut drain = arr.drain(2..8);
+        assert_eq!(drain.as_slice(), ["2", "3", "4", "5", "6", "7"]);
+        drain.next();
+        drain.next_back();
+        assert_eq!(drain.as_slice(), ["3", "4", "5", "6"]);
+    }
 }
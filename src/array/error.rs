@@ -0,0 +1,61 @@
+use std::{alloc::Layout, fmt::Display};
+
+/// The error returned by [super::Array::try_reserve] and [super::Array::try_reserve_exact].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity would exceed `isize::MAX` bytes.
+    CapacityOverflow,
+    /// The allocator failed to provide a buffer of the requested layout.
+    AllocError { layout: Layout },
+}
+
+impl Display for TryReserveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => f.write_str("capacity overflow"),
+            TryReserveError::AllocError { layout } => write!(
+                f,
+                "memory allocation of {size} bytes failed",
+                size = layout.size()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
+
+/// The error returned by [super::Array::try_drain] for a range that is out of bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrainError {
+    /// The range starts past the end of the array.
+    StartOutOfBounds { start: usize, len: usize },
+    /// The range ends past the end of the array.
+    EndOutOfBounds { end: usize, len: usize },
+    /// The range starts after it ends.
+    StartPastEnd {
+        start: usize,
+        end: usize,
+        len: usize,
+    },
+}
+
+impl Display for DrainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrainError::StartOutOfBounds { start, len } => write!(
+                f,
+                "Start index {start} would be out of bounds for Array of length {len}"
+            ),
+            DrainError::EndOutOfBounds { end, len } => write!(
+                f,
+                "End index {end} would be out of bounds for Array of length {len}"
+            ),
+            DrainError::StartPastEnd { start, end, len } => write!(
+                f,
+                "Start index {start} would be past end index {end} for Array of length {len}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DrainError {}
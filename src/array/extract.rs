@@ -0,0 +1,123 @@
+use super::Array;
+use std::{alloc::Allocator, iter::FusedIterator};
+
+impl<T, A: Allocator> Array<T, A> {
+    /// Returns an iterator that removes and yields every element for which `pred` returns true.
+    /// Elements for which `pred` returns false are kept in their original order.
+    /// Dropping the iterator early keeps all elements that have not been visited yet.
+    pub fn extract_if<'a, F: FnMut(&mut T) -> bool>(
+        &'a mut self,
+        pred: F,
+    ) -> ExtractIf<'a, T, A, F> {
+        let old_len = self.len();
+        // If the iterator is leaked, the remaining elements are leaked with it instead of being
+        // left in an inconsistent state.
+        unsafe { self.set_len(0) };
+        ExtractIf {
+            arr: self,
+            idx: 0,
+            del: 0,
+            old_len,
+            pred,
+        }
+    }
+}
+
+pub struct ExtractIf<'a, T, A: Allocator, F: FnMut(&mut T) -> bool> {
+    arr: &'a mut Array<T, A>,
+    /// The index of the next element to visit.
+    idx: usize,
+    /// The number of elements removed so far.
+    del: usize,
+    /// The length of the array before extraction started.
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T, A: Allocator, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'a, T, A, F> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.idx < self.old_len {
+            unsafe {
+                let current = self.arr.idx_to_ptr(self.idx);
+                // If the predicate panics, idx still points at the current element, so Drop
+                // keeps it.
+                let extract = (self.pred)(&mut *current);
+                self.idx += 1;
+                if extract {
+                    self.del += 1;
+                    return Some(current.read());
+                }
+                if self.del > 0 {
+                    current.copy_to_nonoverlapping(current.sub(self.del), 1);
+                }
+            }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<'a, T, A: Allocator, F: FnMut(&mut T) -> bool> FusedIterator for ExtractIf<'a, T, A, F> {}
+
+impl<'a, T, A: Allocator, F: FnMut(&mut T) -> bool> Drop for ExtractIf<'a, T, A, F> {
+    fn drop(&mut self) {
+        // Close the gap left by the extracted elements, keeping the unvisited ones.
+        unsafe {
+            if self.del > 0 {
+                let from = self.arr.idx_to_ptr(self.idx);
+                from.copy_to(from.sub(self.del), self.old_len - self.idx);
+            }
+            self.arr.set_len(self.old_len - self.del);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{tracked, values};
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn extract_evens() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..10, &drops);
+        let extracted: Vec<_> = arr.extract_if(|t| t.0 % 2 == 0).collect();
+        assert_eq!(values(&extracted), [0, 2, 4, 6, 8]);
+        assert_eq!(values(&arr), [1, 3, 5, 7, 9]);
+        assert_eq!(drops.get(), 0);
+    }
+
+    #[test]
+    fn early_drop_keeps_unvisited() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..10, &drops);
+        let mut iter = arr.extract_if(|t| t.0 % 3 == 0);
+        assert_eq!(iter.next().map(|t| t.0), Some(0));
+        assert_eq!(iter.next().map(|t| t.0), Some(3));
+        drop(iter);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(values(&arr), [1, 2, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn panicking_predicate() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..8, &drops);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.extract_if(|t| match t.0 {
+                5 => panic!("predicate"),
+                n => n % 2 == 0,
+            })
+            .for_each(drop);
+        }));
+        assert!(result.is_err());
+        // 0, 2 and 4 were extracted and dropped, the element the predicate panicked on is kept.
+        assert_eq!(drops.get(), 3);
+        assert_eq!(values(&arr), [1, 3, 5, 6, 7]);
+        drop(arr);
+        assert_eq!(drops.get(), 8);
+    }
+}
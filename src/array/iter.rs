@@ -1,11 +1,33 @@
 use super::Array;
-use std::{alloc::Allocator, mem::ManuallyDrop};
+use std::{alloc::Allocator, fmt::Debug, iter::FusedIterator, mem::ManuallyDrop, num::NonZero};
 
 pub struct IntoIter<T, A: Allocator> {
     pub(super) storage: ManuallyDrop<Array<T, A>>,
     pub(super) start: usize,
 }
 
+impl<T, A: Allocator> IntoIter<T, A> {
+    /// Returns the elements that have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        &self.storage[self.start..]
+    }
+    /// Returns the elements that have not been yielded yet, allowing them to be modified.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let start = self.start;
+        &mut self.storage[start..]
+    }
+    /// Returns a reference to the allocator backing the iterator's buffer.
+    pub fn allocator(&self) -> &A {
+        &self.storage.alloc
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
 impl<'s, T, A: Allocator> IntoIterator for &'s Array<T, A> {
     type IntoIter = core::slice::Iter<'s, T>;
     type Item = &'s T;
@@ -46,17 +68,32 @@ impl<T, A: Allocator> Iterator for IntoIter<T, A> {
         Some(val)
     }
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let old_start = self.start;
-        self.start = old_start.saturating_add(n).min(self.storage.len);
-        let removed = old_start..self.start;
-        for idx in removed {
-            unsafe {
-                let val = self.storage.idx_to_ptr(idx);
-                val.drop_in_place();
-            }
-        }
+        self.advance_by(n).ok()?;
         self.next()
     }
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let step = n.min(self.len());
+        let old_start = self.start;
+        self.start += step;
+        // Advance first, drop_in_place on a slice keeps dropping the remaining elements if one of
+        // them panics.
+        unsafe { drop_range(&self.storage, old_start, step) };
+        NonZero::new(n - step).map_or(Ok(()), Err)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+    fn count(self) -> usize {
+        let len = self.len();
+        // Dropping the iterator drops the remaining elements in bulk and frees the buffer.
+        drop(self);
+        len
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        // Read the final element directly, the rest are dropped in bulk with the iterator.
+        self.next_back()
+    }
 }
 impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
     fn len(&self) -> usize {
@@ -71,30 +108,252 @@ impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
         unsafe { Some(self.storage.pop_unchecked()) }
     }
     fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        let old_end = self.storage.len;
-        self.storage.len = old_end.saturating_sub(n).max(self.start);
-        let removed = self.storage.len..old_end;
-        for idx in removed {
-            unsafe {
-                let val = self.storage.idx_to_ptr(idx);
-                val.drop_in_place();
-            }
-        }
-        self.next()
+        self.advance_back_by(n).ok()?;
+        self.next_back()
     }
+    fn advance_back_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        let step = n.min(self.len());
+        self.storage.len -= step;
+        unsafe { drop_range(&self.storage, self.storage.len, step) };
+        NonZero::new(n - step).map_or(Ok(()), Err)
+    }
+}
+/// Drops the `count` elements starting at `start`, which must no longer be part of the iterator.
+unsafe fn drop_range<T, A: Allocator>(storage: &Array<T, A>, start: usize, count: usize) {
+    let removed = core::ptr::slice_from_raw_parts_mut(storage.idx_to_ptr(start), count);
+    unsafe { removed.drop_in_place() };
 }
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
 impl<T, A: Allocator> Drop for IntoIter<T, A> {
     fn drop(&mut self) {
+        /// Frees the buffer and drops the allocator, even if a remaining element panics on drop.
+        struct DeallocGuard<'a, T, A: Allocator>(&'a mut IntoIter<T, A>);
+        impl<'a, T, A: Allocator> Drop for DeallocGuard<'a, T, A> {
+            fn drop(&mut self) {
+                let storage = &mut self.0.storage;
+                unsafe {
+                    if size_of::<T>() != 0 && storage.cap != 0 {
+                        storage.alloc.deallocate(
+                            storage.buf.cast(),
+                            Array::<T>::layout_for_len(storage.cap),
+                        );
+                    }
+                    core::ptr::drop_in_place(&mut storage.alloc);
+                }
+            }
+        }
+        let guard = DeallocGuard(self);
         // Drop all remaining elements
-        _ = self.nth(usize::MAX);
-        if size_of::<T>() == 0 {
-            return;
+        _ = guard.0.nth(usize::MAX);
+    }
+}
+
+impl<T, A: Allocator> Array<T, A> {
+    /// Consumes the array, yielding its elements in owned chunks of `N`.
+    /// The elements that don't fill a whole chunk can be inspected with [IntoChunks::remainder],
+    /// and moved out with [IntoChunks::into_inner] once the iterator is exhausted.
+    /// # Panics
+    /// Panics if `N` is 0.
+    pub fn into_chunks<const N: usize>(self) -> IntoChunks<T, N, A> {
+        assert!(N != 0, "Chunk size must be non-zero");
+        IntoChunks {
+            iter: self.into_iter(),
         }
+    }
+}
+
+/// An iterator over the elements of an [Array] in owned chunks of `N`, created by
+/// [Array::into_chunks].
+pub struct IntoChunks<T, const N: usize, A: Allocator> {
+    iter: IntoIter<T, A>,
+}
+
+impl<T, const N: usize, A: Allocator> IntoChunks<T, N, A> {
+    /// Returns the trailing elements that will not fill a whole chunk.
+    pub fn remainder(&self) -> &[T] {
+        let rest = self.iter.as_slice();
+        &rest[rest.len() - rest.len() % N..]
+    }
+    /// Consumes the iterator, returning all the elements that have not been yielded yet,
+    /// including any whole chunks.
+    /// Once the iterator is exhausted, these are exactly the elements of [Self::remainder].
+    pub fn into_inner(self) -> IntoIter<T, A> {
+        self.iter
+    }
+}
+
+impl<T: Debug, const N: usize, A: Allocator> Debug for IntoChunks<T, N, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IntoChunks")
+            .field(&self.iter.as_slice())
+            .finish()
+    }
+}
+
+impl<T, const N: usize, A: Allocator> Iterator for IntoChunks<T, N, A> {
+    type Item = [T; N];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter.len() < N {
+            return None;
+        }
+        let mut chunk = std::mem::MaybeUninit::<[T; N]>::uninit();
         unsafe {
-            self.storage.alloc.deallocate(
-                self.storage.buf.cast(),
-                Array::<T>::layout_for_len(self.storage.buf.len()),
-            );
+            self.iter
+                .storage
+                .idx_to_ptr(self.iter.start)
+                .copy_to_nonoverlapping(chunk.as_mut_ptr().cast(), N);
+            self.iter.start += N;
+            Some(chunk.assume_init())
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize, A: Allocator> ExactSizeIterator for IntoChunks<T, N, A> {
+    fn len(&self) -> usize {
+        self.iter.len() / N
+    }
+}
+
+impl<T, const N: usize, A: Allocator> FusedIterator for IntoChunks<T, N, A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{
+        Array,
+        tests::{Tagged, Tracked, strings, tracked},
+    };
+    use std::{cell::Cell, num::NonZero, rc::Rc};
+
+    #[test]
+    fn into_chunks_exact_multiple() {
+        let mut chunks = strings(0..6).into_chunks::<3>();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.remainder().is_empty());
+        assert_eq!(chunks.next(), Some(["0", "1", "2"].map(String::from)));
+        assert_eq!(chunks.next(), Some(["3", "4", "5"].map(String::from)));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.into_inner().len(), 0);
+    }
+
+    #[test]
+    fn into_chunks_with_remainder() {
+        let mut chunks = strings(0..8).into_chunks::<3>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.remainder(), ["6", "7"]);
+        assert_eq!(chunks.next(), Some(["0", "1", "2"].map(String::from)));
+        assert_eq!(chunks.remainder(), ["6", "7"]);
+        assert!(chunks.by_ref().last().is_some());
+        let rest: Vec<_> = chunks.into_inner().collect();
+        assert_eq!(rest, ["6", "7"]);
+    }
+
+    #[test]
+    fn into_inner_includes_unyielded_chunks() {
+        let mut chunks = strings(0..8).into_chunks::<3>();
+        chunks.next();
+        let rest: Vec<_> = chunks.into_inner().collect();
+        assert_eq!(rest, ["3", "4", "5", "6", "7"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Chunk size must be non-zero")]
+    fn into_chunks_zero() {
+        strings(0..3).into_chunks::<0>();
+    }
+
+    #[test]
+    fn count_after_nth() {
+        let mut iter = strings(0..7).into_iter();
+        assert_eq!(iter.nth(2).as_deref(), Some("2"));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.count(), 4);
+    }
+
+    #[test]
+    fn panicking_drop_frees_buffer() {
+        /// Panics when dropped if the flag is set, after which its fields are still dropped.
+        struct PanicOnDrop(#[allow(dead_code)] Tracked, bool);
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                if self.1 {
+                    panic!("drop");
+                }
+            }
+        }
+        let drops = Rc::new(Cell::new(0));
+        let arr: Array<PanicOnDrop> = (0..5)
+            .map(|n| PanicOnDrop(Tracked(n, drops.clone()), n == 1))
+            .collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(arr)));
+        assert!(result.is_err());
+        // Miri reports the buffer as leaked if it is not freed while unwinding.
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn advance_past_end_returns_shortfall() {
+        let drops = Rc::new(Cell::new(0));
+        let mut iter = tracked(0..5, &drops).into_iter();
+        assert_eq!(iter.advance_by(2), Ok(()));
+        assert_eq!(iter.advance_back_by(1), Ok(()));
+        assert_eq!(iter.advance_by(4), Err(NonZero::new(2).unwrap()));
+        assert_eq!(drops.get(), 5);
+        assert_eq!(iter.advance_back_by(3), Err(NonZero::new(3).unwrap()));
+        assert_eq!(iter.advance_by(0), Ok(()));
+        drop(iter);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn last_matches_vec() {
+        let drops = Rc::new(Cell::new(0));
+        let last = tracked(0..5, &drops).into_iter().last();
+        let vec_drops = Rc::new(Cell::new(0));
+        let vec_last = Vec::from(tracked(0..5, &vec_drops)).into_iter().last();
+        assert_eq!(last.as_ref().map(|t| t.0), vec_last.as_ref().map(|t| t.0));
+        assert_eq!((drops.get(), vec_drops.get()), (4, 4));
+        drop((last, vec_last));
+        assert_eq!((drops.get(), vec_drops.get()), (5, 5));
+
+        let mut iter = tracked(0..5, &drops).into_iter();
+        iter.next_back();
+        iter.next();
+        assert_eq!(iter.last().map(|t| t.0), Some(3));
+        assert!(Array::<Tracked>::new().into_iter().last().is_none());
+    }
+
+    #[test]
+    fn size_hint_after_nth() {
+        let mut iter = strings(0..10).into_iter();
+        assert_eq!(iter.size_hint(), (10, Some(10)));
+        iter.nth(3);
+        assert_eq!(iter.size_hint(), (6, Some(6)));
+        iter.nth_back(1);
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.nth(10);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn fused_after_exhaustion() {
+        let mut iter = strings(0..2).into_iter();
+        assert_eq!(iter.by_ref().count(), 2);
+        for _ in 0..3 {
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+        }
+    }
+
+    #[test]
+    fn allocator_is_kept() {
+        let arr = Array::from_iter_in([1, 2, 3], Tagged(4));
+        let mut iter = arr.into_iter();
+        assert_eq!(iter.allocator(), &Tagged(4));
+        iter.next();
+        assert_eq!(iter.allocator(), &Tagged(4));
+    }
 }
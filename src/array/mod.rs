@@ -1,34 +1,243 @@
 pub mod iter;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
 mod drain;
+mod error;
+mod extract;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "serde")]
+mod serde;
+mod splice;
+mod write;
 use std::{
-    alloc::{Allocator, Global, Layout},
+    alloc::{Allocator, Global, Layout, handle_alloc_error},
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    convert::Infallible,
     fmt::Debug,
-    mem::MaybeUninit,
-    ops::{Deref, DerefMut},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds},
     ptr::NonNull,
+    slice::SliceIndex,
 };
 
+#[cfg(feature = "rayon")]
+pub use self::rayon::IntoParIter;
+pub use error::{DrainError, TryReserveError};
+pub use extract::ExtractIf;
+pub use splice::Splice;
+
+/// Creates an [Array] containing the arguments, like [vec!].
+/// The macro lives at the crate root, import it with `use structures::array;`.
+/// Supports `array![]`, `array![a, b, c]` and `array![value; n]`, the latter requiring
+/// `value: Clone`.
+#[macro_export]
+macro_rules! array {
+    () => {
+        $crate::array::Array::new()
+    };
+    ($value:expr; $n:expr) => {
+        $crate::array::Array::from_elem($value, $n)
+    };
+    ($($value:expr),+ $(,)?) => {{
+        let mut arr = $crate::array::Array::with_capacity([$(stringify!($value)),+].len());
+        $(arr.push($value);)+
+        arr
+    }};
+}
+
 pub struct Array<T, A: Allocator = Global> {
     len: usize,
-    buf: NonNull<[MaybeUninit<T>]>,
+    cap: usize,
+    buf: NonNull<MaybeUninit<T>>,
     alloc: A,
 }
 // SAFETY: This impl tells the compiler that the Array type is okay to [Send] accross threads,
-// if the type being stored is okay to send accross threads.
-unsafe impl<T: Send> Send for Array<T> {}
+// if the type being stored and the allocator are okay to send accross threads.
+unsafe impl<T: Send, A: Allocator + Send> Send for Array<T, A> {}
 // SAFETY: This impl tells the compiler that the Array type is okay to share accross threads,
-// meaning Send around references to the Array, if that is allowed for the type being stored.
-unsafe impl<T: Sync> Sync for Array<T> {}
+// meaning Send around references to the Array, if that is allowed for the type being stored and
+// the allocator.
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for Array<T, A> {}
 
 impl<T> Array<T> {
     pub const fn new() -> Self {
         Array::new_in(Global)
     }
+    /// Creates an empty array with room for at least `cap` elements.
+    pub fn with_capacity(cap: usize) -> Self {
+        Array::with_capacity_in(cap, Global)
+    }
+    /// Decomposes the array into its raw pointer, length and capacity.
+    /// The array can be rebuilt with [Self::from_raw_parts].
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let (ptr, len, cap, Global) = self.into_raw_parts_with_alloc();
+        (ptr, len, cap)
+    }
+    /// Rebuilds an array from the parts returned by [Self::into_raw_parts].
+    /// # Safety
+    /// See [Self::from_raw_parts_in].
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize, cap: usize) -> Self {
+        unsafe { Array::from_raw_parts_in(ptr, len, cap, Global) }
+    }
+    /// Transforms every element with `f`, producing a new array.
+    /// If `U` has the same size and alignment as `T`, the map happens in place and the buffer is
+    /// reused, otherwise a new buffer is allocated.
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> Array<U> {
+        let Ok(arr) = self.try_map(|value| Ok::<U, Infallible>(f(value)));
+        arr
+    }
+    /// Transforms every element with `f`, producing a new array, or returns the first error.
+    /// On error, the elements that were already transformed and the ones not yet visited are
+    /// dropped.
+    /// Reuses the buffer under the same conditions as [Self::map].
+    pub fn try_map<U, E, F: FnMut(T) -> Result<U, E>>(self, mut f: F) -> Result<Array<U>, E> {
+        if size_of::<U>() != size_of::<T>() || align_of::<U>() != align_of::<T>() {
+            return self.into_iter().map(f).collect();
+        }
+        /// Drops the mapped and unmapped elements and frees the buffer if `f` fails or panics.
+        struct MapGuard<T, U> {
+            ptr: *mut T,
+            len: usize,
+            cap: usize,
+            /// The index of the element currently being mapped.
+            current: usize,
+            _marker: PhantomData<U>,
+        }
+        impl<T, U> Drop for MapGuard<T, U> {
+            fn drop(&mut self) {
+                unsafe {
+                    let unmapped = self.len - self.current - 1;
+                    core::ptr::slice_from_raw_parts_mut(self.ptr.add(self.current + 1), unmapped)
+                        .drop_in_place();
+                    core::ptr::slice_from_raw_parts_mut(self.ptr.cast::<U>(), self.current)
+                        .drop_in_place();
+                    drop(Array::from_raw_parts(self.ptr, 0, self.cap));
+                }
+            }
+        }
+        let (ptr, len, cap) = self.into_raw_parts();
+        let mut guard = MapGuard::<T, U> {
+            ptr,
+            len,
+            cap,
+            current: 0,
+            _marker: PhantomData,
+        };
+        while guard.current < len {
+            unsafe {
+                let slot = ptr.add(guard.current);
+                let mapped = f(slot.read())?;
+                slot.cast::<U>().write(mapped);
+            }
+            guard.current += 1;
+        }
+        core::mem::forget(guard);
+        // SAFETY: Every element has been replaced by a U, which has the same layout as T.
+        unsafe { Ok(Array::from_raw_parts(ptr.cast(), len, cap)) }
+    }
+}
+
+impl<T: Clone> Array<T> {
+    /// Creates an array of `n` clones of `value`, moving `value` itself into the last slot.
+    /// Does not allocate if `n` is 0.
+    pub fn from_elem(value: T, n: usize) -> Array<T> {
+        let mut arr = Array::with_capacity(n);
+        arr.resize(n, value);
+        arr
+    }
+}
+
+/// Resolves `range` into a start and end index, for an array of length `len`.
+/// The result is not bounds checked.
+fn range_to_bounds(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.saturating_add(1),
+    };
+    let end = match range.end_bound() {
+        Bound::Unbounded => len,
+        Bound::Included(&n) => n.saturating_add(1),
+        Bound::Excluded(&n) => n,
+    };
+    (start, end)
+}
+
+/// Writes the tracked length back into the array when dropped, so elements written before a
+/// panic stay owned by the array.
+struct SetLenOnDrop<'a> {
+    len: &'a mut usize,
+    local_len: usize,
+}
+
+impl<'a> SetLenOnDrop<'a> {
+    fn new(len: &'a mut usize) -> Self {
+        let local_len = *len;
+        SetLenOnDrop { len, local_len }
+    }
+}
+
+impl Drop for SetLenOnDrop<'_> {
+    fn drop(&mut self) {
+        *self.len = self.local_len;
+    }
+}
+
+/// Compacts the kept elements of an array towards its start while it is being filtered.
+/// The elements below `write` are kept, `write..read` are holes and `read..len` are unvisited.
+/// When dropped, even by a panic, the unvisited elements are shifted down over the holes and the
+/// length is restored.
+struct CompactGuard<'a, T, A: Allocator> {
+    write: usize,
+    read: usize,
+    len: usize,
+    arr: &'a mut Array<T, A>,
+}
+
+impl<'a, T, A: Allocator> CompactGuard<'a, T, A> {
+    /// Starts compacting `arr`, keeping its first `start` elements.
+    fn new(arr: &'a mut Array<T, A>, start: usize) -> Self {
+        let len = arr.len;
+        // Elements are moved out of the array while the caller's closures run, hide them until
+        // the guard restores the length.
+        arr.len = 0;
+        CompactGuard {
+            write: start,
+            read: start,
+            len,
+            arr,
+        }
+    }
+    /// Moves the current element down into the first hole, keeping it.
+    /// # Safety
+    /// `self.read` must be less than `self.len`.
+    unsafe fn keep_current(&mut self) {
+        if self.read != self.write {
+            unsafe {
+                self.arr
+                    .idx_to_ptr(self.read)
+                    .copy_to_nonoverlapping(self.arr.idx_to_ptr(self.write), 1);
+            }
+        }
+        self.write += 1;
+        self.read += 1;
+    }
 }
 
-fn bytes_to_t<T>(bytes: NonNull<[u8]>) -> NonNull<[T]> {
-    let new_len = bytes.len() / size_of::<T>();
-    NonNull::slice_from_raw_parts(bytes.cast(), new_len)
+impl<'a, T, A: Allocator> Drop for CompactGuard<'a, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let remaining = self.len - self.read;
+            self.arr
+                .idx_to_ptr(self.read)
+                .copy_to(self.arr.idx_to_ptr(self.write), remaining);
+            self.arr.len = self.write + remaining;
+        }
+    }
 }
 
 impl<T, A: Allocator> Array<T, A> {
@@ -36,56 +245,176 @@ impl<T, A: Allocator> Array<T, A> {
         let cap = if size_of::<T>() == 0 { usize::MAX } else { 0 };
         Array {
             len: 0,
-            buf: NonNull::slice_from_raw_parts(NonNull::dangling(), cap),
+            cap,
+            buf: NonNull::dangling(),
+            alloc,
+        }
+    }
+    /// Collects the elements of `iter` into a new array in the provided allocator, reserving
+    /// room for the lower bound of its size hint up front.
+    pub fn from_iter_in<I: IntoIterator<Item = T>>(iter: I, alloc: A) -> Self {
+        let iter = iter.into_iter();
+        let mut arr = Array::with_capacity_in(iter.size_hint().0, alloc);
+        arr.extend(iter);
+        arr
+    }
+    /// Decomposes the array into its raw pointer, length, capacity and allocator.
+    /// The array can be rebuilt with [Self::from_raw_parts_in].
+    pub fn into_raw_parts_with_alloc(self) -> (*mut T, usize, usize, A) {
+        let this = ManuallyDrop::new(self);
+        let alloc = unsafe { core::ptr::read(&this.alloc) };
+        (this.idx_to_ptr(0), this.len, this.cap, alloc)
+    }
+    /// Rebuilds an array from the parts returned by [Self::into_raw_parts_with_alloc].
+    /// # Safety
+    /// `ptr`, `cap` and `alloc` must come from a previous call to
+    /// [Self::into_raw_parts_with_alloc] (or [Array::into_raw_parts] for [Global]), and the first
+    /// `len` elements must be initialized, with `len <= cap`.
+    pub const unsafe fn from_raw_parts_in(ptr: *mut T, len: usize, cap: usize, alloc: A) -> Self {
+        Array {
+            len,
+            cap,
+            buf: unsafe { NonNull::new_unchecked(ptr.cast()) },
             alloc,
         }
     }
+    /// Creates an empty array in the provided allocator with room for at least `cap` elements.
+    pub fn with_capacity_in(cap: usize, alloc: A) -> Self {
+        let mut arr = Array::new_in(alloc);
+        if size_of::<T>() != 0 && cap != 0 {
+            unsafe {
+                arr.grow_to_cap(cap);
+            }
+        }
+        arr
+    }
+    /// The smallest non-zero capacity allocated by [Self::reserve]: at least 4 elements, or a
+    /// full cache line for small types.
+    const MIN_CAP: usize = if size_of::<T>() == 0 || size_of::<T>() > 16 {
+        4
+    } else {
+        64 / size_of::<T>()
+    };
     #[inline]
     fn layout_for_len(len: usize) -> Layout {
         Layout::array::<T>(len).unwrap()
     }
+    /// Grows the buffer to hold `new_cap` elements, deferring to [handle_alloc_error] if the
+    /// allocator fails.
     unsafe fn grow_to_cap(&mut self, new_cap: usize) {
-        let layout = Self::layout_for_len(new_cap);
-        if self.buf.is_empty() {
+        match unsafe { self.try_grow_to_cap(new_cap) } {
+            Ok(()) => {}
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => handle_alloc_error(layout),
+        }
+    }
+    /// Fallible version of [Self::grow_to_cap], leaving the array untouched on failure.
+    unsafe fn try_grow_to_cap(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        let layout = Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
+        if self.cap == 0 {
             // Need to allocate a new buffer.
-            let allocated = self.alloc.allocate(layout).unwrap();
-            self.buf = bytes_to_t(allocated);
-            return;
+            // A zero capacity always means `buf` is the dangling sentinel from `new_in`, as
+            // `shrink_to` deallocates instead of shrinking to a zero-sized layout.
+            let allocated = self
+                .alloc
+                .allocate(layout)
+                .map_err(|_| TryReserveError::AllocError { layout })?;
+            self.buf = allocated.cast();
+            self.cap = new_cap;
+            return Ok(());
         }
         // Need to reallocate.
         unsafe {
             let new_buf = self
                 .alloc
-                .grow(
-                    self.buf.cast(),
-                    Self::layout_for_len(self.buf.len()),
-                    Self::layout_for_len(new_cap),
-                )
-                .unwrap();
-            self.buf = bytes_to_t(new_buf);
+                .grow(self.buf.cast(), Self::layout_for_len(self.cap), layout)
+                .map_err(|_| TryReserveError::AllocError { layout })?;
+            self.buf = new_buf.cast();
+            self.cap = new_cap;
         }
+        Ok(())
+    }
+    /// The capacity [Self::reserve] grows to when at least `new_min` elements are needed.
+    fn amortized_cap(&self, new_min: usize) -> usize {
+        // Doubling keeps pushes amortized O(1), while a large request jumps straight to the
+        // requested size.
+        new_min.max(self.cap.saturating_mul(2)).max(Self::MIN_CAP)
     }
     /// Ensures at least `additional` elements can be inserted into the array without requiring a
     /// reallocation.
+    /// # Panics
+    /// Panics with "capacity overflow" if the new capacity would exceed `isize::MAX` bytes.
     pub fn reserve(&mut self, additional: usize) {
-        let new_min = self.len + additional;
-        if self.buf.len() >= new_min {
+        let Some(new_min) = self.len.checked_add(additional) else {
+            panic!("capacity overflow");
+        };
+        if self.cap >= new_min {
             // No reallocation necessary
             return;
         }
-        let new_cap = new_min.next_power_of_two();
+        let new_cap = self.amortized_cap(new_min);
         unsafe {
             self.grow_to_cap(new_cap);
         }
     }
+    /// Fallible version of [Self::reserve], returning an error instead of panicking if the
+    /// capacity overflows or the allocator fails. The array is left unchanged on failure.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let new_min = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if self.cap >= new_min {
+            // No reallocation necessary
+            return Ok(());
+        }
+        let new_cap = self.amortized_cap(new_min);
+        unsafe { self.try_grow_to_cap(new_cap) }
+    }
+    /// Ensures at least `additional` elements can be inserted into the array without requiring a
+    /// reallocation, without over-allocating.
+    /// Prefer [Self::reserve] when more elements are likely to be pushed later.
+    /// # Panics
+    /// Panics with "capacity overflow" if the new capacity would exceed `isize::MAX` bytes.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let Some(new_min) = self.len.checked_add(additional) else {
+            panic!("capacity overflow");
+        };
+        if self.cap >= new_min {
+            // No reallocation necessary
+            return;
+        }
+        unsafe {
+            self.grow_to_cap(new_min);
+        }
+    }
+    /// Fallible version of [Self::reserve_exact], returning an error instead of panicking if the
+    /// capacity overflows or the allocator fails. The array is left unchanged on failure.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let new_min = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if self.cap >= new_min {
+            // No reallocation necessary
+            return Ok(());
+        }
+        unsafe { self.try_grow_to_cap(new_min) }
+    }
+    /// Appends `value` to the end of the array.
+    /// # Panics
+    /// Panics with "capacity overflow" if the array would exceed `isize::MAX` bytes, or for
+    /// zero-sized types, if the length would exceed `usize::MAX`.
     pub fn push(&mut self, value: T) {
+        // For zero-sized types the capacity is usize::MAX, so reserve's checked length is what
+        // stops the length from wrapping.
         self.reserve(1);
         unsafe {
             self.push_within_capacity_unchecked(value);
         }
     }
     pub fn push_within_capacity(&mut self, value: T) -> Result<(), T> {
-        if self.len == self.buf.len() {
+        if self.len == self.cap {
             return Err(value);
         }
         unsafe { self.push_within_capacity_unchecked(value) };
@@ -102,6 +431,21 @@ impl<T, A: Allocator> Array<T, A> {
             self.len += 1;
         }
     }
+    /// Returns a pointer to the start of the buffer, valid for reading `len` elements.
+    /// The pointer is never null, for empty arrays and zero-sized types it is dangling but
+    /// aligned.
+    /// Any reallocation of the array invalidates the pointer.
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr().cast()
+    }
+    /// Returns a pointer to the start of the buffer, valid for reading and writing `len`
+    /// elements.
+    /// The pointer is never null, for empty arrays and zero-sized types it is dangling but
+    /// aligned.
+    /// Any reallocation of the array invalidates the pointer.
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_ptr().cast()
+    }
     const fn idx_to_ptr(&self, idx: usize) -> *mut T {
         unsafe { self.buf.as_ptr().cast::<T>().add(idx) }
     }
@@ -111,13 +455,42 @@ impl<T, A: Allocator> Array<T, A> {
         }
         unsafe { Some(self.pop_unchecked()) }
     }
+    /// Inserts `value` at the start of the array.
+    /// This shifts every element to the right, so it runs in O(n) time.
+    pub fn push_front(&mut self, value: T) {
+        self.insert(0, value);
+    }
+    /// Removes the first element and returns it, or [None] if the array is empty.
+    /// This shifts every remaining element to the left, so it runs in O(n) time.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.try_remove(0)
+    }
+    /// Equivalent to [Self::pop], but will not check that the array is non-empty.
+    /// # Safety
+    /// Requires that the array contains at least one element.
     pub unsafe fn pop_unchecked(&mut self) -> T {
         unsafe {
             self.len = self.len.checked_sub(1).unwrap_unchecked();
             self.idx_to_ptr(self.len).read()
         }
     }
-    pub fn remove(&mut self, idx: usize) -> Option<T> {
+    /// Removes and returns the element at `idx`, shifting all elements after it to the left.
+    /// This matches [Vec::remove], see [Self::try_remove] for a non-panicking version.
+    /// # Panics
+    /// Panics if `idx >= self.len()`.
+    #[track_caller]
+    pub fn remove(&mut self, idx: usize) -> T {
+        match self.try_remove(idx) {
+            Some(value) => value,
+            None => panic!(
+                "Index {idx} would be out of bounds for Array of length {len}",
+                len = self.len
+            ),
+        }
+    }
+    /// Removes and returns the element at `idx`, shifting all elements after it to the left.
+    /// Returns [None] if `idx` is out of bounds.
+    pub fn try_remove(&mut self, idx: usize) -> Option<T> {
         if idx >= self.len {
             return None;
         }
@@ -132,25 +505,142 @@ impl<T, A: Allocator> Array<T, A> {
     pub const fn len(&self) -> usize {
         self.len
     }
-    pub const unsafe fn set_len(&mut self, len: usize){
-        self.len = len;
+    /// Returns a reference to the element or subslice at `index`, or [None] if it is out of
+    /// bounds.
+    #[inline]
+    pub fn get<I: SliceIndex<[T]>>(&self, index: I) -> Option<&I::Output> {
+        (**self).get(index)
+    }
+    /// Returns a mutable reference to the element or subslice at `index`, or [None] if it is out
+    /// of bounds.
+    #[inline]
+    pub fn get_mut<I: SliceIndex<[T]>>(&mut self, index: I) -> Option<&mut I::Output> {
+        (**self).get_mut(index)
+    }
+    /// Returns mutable references to the elements at all of `indices` at once.
+    /// Returns [None] if any index is out of bounds, or if any two indices are equal.
+    pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        for (i, &idx) in indices.iter().enumerate() {
+            if idx >= self.len || indices[..i].contains(&idx) {
+                return None;
+            }
+        }
+        // SAFETY: All indices are in bounds and distinct, so the references never alias.
+        Some(indices.map(|idx| unsafe { &mut *self.idx_to_ptr(idx) }))
+    }
+    /// Returns the first element, or [None] if the array is empty.
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        (**self).first()
+    }
+    /// Returns a mutable reference to the first element, or [None] if the array is empty.
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        (**self).first_mut()
+    }
+    /// Returns the last element, or [None] if the array is empty.
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        (**self).last()
+    }
+    /// Returns a mutable reference to the last element, or [None] if the array is empty.
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        (**self).last_mut()
+    }
+    /// Returns an iterator over all overlapping pairs of adjacent elements.
+    pub fn pairs(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.iter().zip(self.iter().skip(1))
+    }
+    /// Returns a reference to the allocator backing the array.
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
+    }
+    /// Returns the number of elements the array can hold without reallocating.
+    /// For zero-sized types this is always [usize::MAX].
+    pub const fn capacity(&self) -> usize {
+        self.cap
+    }
+    /// Sets the length of the array without dropping or initializing any elements.
+    /// Usually paired with [Self::spare_capacity_mut], to commit elements written into the spare
+    /// capacity.
+    /// # Safety
+    /// Requires that `new_len <= self.capacity()`, and that the first `new_len` elements are
+    /// initialized.
+    /// Elements past `new_len` are not dropped, and will be leaked unless ownership of them is
+    /// taken elsewhere.
+    pub const unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
+    /// Returns the uninitialized spare capacity of the array, the slots `len..capacity`.
+    /// Values written there become part of the array after a call to [Self::set_len].
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            core::slice::from_raw_parts_mut(self.buf.as_ptr().add(self.len), self.cap - self.len)
+        }
+    }
+    /// Returns the initialized elements and the uninitialized spare capacity at the same time.
+    /// See [Self::spare_capacity_mut].
+    pub fn split_at_spare_mut(&mut self) -> (&mut [T], &mut [MaybeUninit<T>]) {
+        unsafe {
+            let base = self.buf.as_ptr();
+            (
+                core::slice::from_raw_parts_mut(base.cast::<T>(), self.len),
+                core::slice::from_raw_parts_mut(base.add(self.len), self.cap - self.len),
+            )
+        }
     }
     pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
-    pub fn insert(&mut self, idx: usize, value: T) -> Result<(), T> {
+    /// Inserts `value` at `idx`, shifting all elements after it to the right.
+    /// This matches [Vec::insert], see [Self::try_insert] for a non-panicking version.
+    /// # Panics
+    /// Panics if `idx > self.len()`.
+    #[track_caller]
+    pub fn insert(&mut self, idx: usize, value: T) {
+        if self.try_insert(idx, value).is_err() {
+            panic!(
+                "Index {idx} would be out of bounds for Array of length {len}",
+                len = self.len
+            );
+        }
+    }
+    /// Inserts `value` at `idx`, shifting all elements after it to the right.
+    /// Returns the value back if `idx` is out of bounds.
+    pub fn try_insert(&mut self, idx: usize, value: T) -> Result<(), T> {
         if idx > self.len {
             return Err(value);
         }
         self.reserve(1);
-        self.len += 1;
         unsafe {
             let insert_at = self.idx_to_ptr(idx);
+            // Shift the elements after idx, which requires room for exactly one more element.
             insert_at.copy_to(insert_at.add(1), self.len - idx);
             insert_at.write(value);
         }
+        self.len += 1;
         Ok(())
     }
+    /// Inserts `value` into a sorted array at the position found by [slice::binary_search_by],
+    /// returning the index it was inserted at.
+    /// If the array contains matching elements, the index of any one of them may be used.
+    pub fn insert_sorted_by(&mut self, value: T, mut f: impl FnMut(&T, &T) -> Ordering) -> usize {
+        let idx = (**self)
+            .binary_search_by(|probe| f(probe, &value))
+            .unwrap_or_else(|idx| idx);
+        self.insert(idx, value);
+        idx
+    }
+    /// Inserts `value` into an array sorted by `key`, returning the index it was inserted at.
+    /// See [Self::insert_sorted_by].
+    pub fn insert_sorted_by_key<K: Ord>(
+        &mut self,
+        value: T,
+        mut key: impl FnMut(&T) -> K,
+    ) -> usize {
+        self.insert_sorted_by(value, |probe, value| key(probe).cmp(&key(value)))
+    }
     /// A remove operation that, instead of preserving order, replaces the element with the last
     /// element in the list.
     /// This allows this operation to always run in O(1) time.
@@ -158,6 +648,10 @@ impl<T, A: Allocator> Array<T, A> {
         if idx >= self.len {
             return None;
         }
+        if idx == self.len - 1 {
+            // Removing the last element, there is nothing to swap with.
+            return unsafe { Some(self.pop_unchecked()) };
+        }
         let swap_with = self.idx_to_ptr(self.len - 1);
         let remove = self.idx_to_ptr(idx);
         unsafe {
@@ -167,109 +661,733 @@ impl<T, A: Allocator> Array<T, A> {
             Some(value)
         }
     }
+    /// Shrinks the capacity to exactly `self.len()`, without any rounding.
+    /// An empty array gives up its allocation entirely.
     pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+    /// Shrinks the capacity to `max(self.len(), min_capacity)`.
+    /// Does nothing if the capacity is already at or below that.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
         if size_of::<T>() == 0 {
             return;
         }
-        if self.len == self.buf.len() {
+        let new_cap = self.len.max(min_capacity);
+        if new_cap >= self.cap {
+            return;
+        }
+        if new_cap == 0 {
+            unsafe {
+                self.alloc
+                    .deallocate(self.buf.cast(), Self::layout_for_len(self.cap))
+            };
+            self.buf = NonNull::dangling();
+            self.cap = 0;
             return;
         }
         unsafe {
-            let ptr = self.alloc.shrink(
-                self.buf.cast(),
-                Self::layout_for_len(self.buf.len()),
-                Self::layout_for_len(self.len),
-            );
-            self.buf = bytes_to_t(ptr.unwrap());
+            let new_layout = Self::layout_for_len(new_cap);
+            let ptr = self
+                .alloc
+                .shrink(self.buf.cast(), Self::layout_for_len(self.cap), new_layout)
+                .unwrap_or_else(|_| handle_alloc_error(new_layout));
+            self.buf = ptr.cast();
+            self.cap = new_cap;
+        }
+    }
+    /// Converts the array into a boxed slice, dropping any spare capacity.
+    /// The elements are neither copied nor dropped.
+    pub fn into_boxed_slice(mut self) -> Box<[T], A> {
+        self.shrink_to_fit();
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let alloc = core::ptr::read(&this.alloc);
+            let slice = core::ptr::slice_from_raw_parts_mut(this.idx_to_ptr(0), this.len);
+            Box::from_raw_in(slice, alloc)
         }
     }
+    /// Consumes the array, returning a mutable reference to its elements that lives for as long
+    /// as the allocator does.
+    /// The allocation is intentionally never freed, and the elements are never dropped.
+    pub fn leak<'a>(mut self) -> &'a mut [T]
+    where
+        A: 'a,
+    {
+        self.shrink_to_fit();
+        let this = ManuallyDrop::new(self);
+        unsafe { this.value_slice().as_mut() }
+    }
     const fn value_slice(&self) -> NonNull<[T]> {
         NonNull::slice_from_raw_parts(self.buf.cast(), self.len)
     }
-    pub fn retain(&mut self, mut pred: impl FnMut(&mut T) -> bool) {
-        struct DropGuard<'a, T, A: Allocator> {
-            write: usize,
+    /// Moves all elements of `other` to the end of `self`, leaving `other` empty.
+    /// The buffer of `other` is kept for reuse.
+    pub fn append(&mut self, other: &mut Array<T, A>) {
+        let count = other.len;
+        self.reserve(count);
+        unsafe {
+            other
+                .idx_to_ptr(0)
+                .copy_to_nonoverlapping(self.idx_to_ptr(self.len), count);
+            other.len = 0;
+            self.len += count;
+        }
+    }
+    /// Resizes the array to `new_len`, either filling the new slots with values returned by `f`,
+    /// or dropping the elements past `new_len`.
+    pub fn resize_with(&mut self, new_len: usize, mut f: impl FnMut() -> T) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+        self.reserve(new_len - self.len);
+        let base = self.idx_to_ptr(0);
+        let mut len = SetLenOnDrop::new(&mut self.len);
+        while len.local_len < new_len {
+            unsafe {
+                base.add(len.local_len).write(f());
+            }
+            len.local_len += 1;
+        }
+    }
+    /// Drops all elements in the array, keeping the allocated buffer for reuse.
+    pub fn clear(&mut self) {
+        let values = self.value_slice();
+        self.len = 0;
+        unsafe {
+            values.drop_in_place();
+        }
+    }
+    /// Drops all elements past `len`, keeping the capacity unchanged.
+    /// Does nothing if `len` is greater than or equal to the current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail = NonNull::slice_from_raw_parts(
+            unsafe { NonNull::new_unchecked(self.idx_to_ptr(len)) },
+            self.len - len,
+        );
+        // Update the length first, so a panicking Drop impl can't cause a double drop.
+        // drop_in_place on a slice keeps dropping the remaining elements if one of them panics.
+        self.len = len;
+        unsafe {
+            tail.drop_in_place();
+        }
+    }
+    /// Removes all elements past `len` like [Self::truncate], but passes each of them to
+    /// `on_drop` in order instead of dropping them.
+    /// If `on_drop` panics, the remaining removed elements are dropped.
+    pub fn truncate_with(&mut self, len: usize, mut on_drop: impl FnMut(T)) {
+        struct DropGuard<T> {
+            base: *mut T,
             read: usize,
-            arr: &'a mut Array<T, A>,
+            end: usize,
         }
-        impl<'a, T, A: Allocator> Drop for DropGuard<'a, T, A> {
+        impl<T> Drop for DropGuard<T> {
             fn drop(&mut self) {
+                let remaining = self.end - self.read;
                 unsafe {
-                    while self.read < self.arr.len {
-                        self.arr.idx_to_ptr(self.read).drop_in_place();
-                        self.read += 1;
-                    }
+                    core::ptr::slice_from_raw_parts_mut(self.base.add(self.read), remaining)
+                        .drop_in_place();
                 }
-                self.arr.len = self.write;
             }
         }
+        if len >= self.len {
+            return;
+        }
         let mut guard = DropGuard {
-            write: 0,
-            read: 0,
-            arr: self,
+            base: self.idx_to_ptr(0),
+            read: len,
+            end: self.len,
         };
-        while guard.read < guard.arr.len {
+        // Update the length first, the guard owns the removed elements from here on.
+        self.len = len;
+        while guard.read < guard.end {
+            let value = unsafe { guard.base.add(guard.read).read() };
+            guard.read += 1;
+            on_drop(value);
+        }
+    }
+    /// Keeps only the elements for which `pred` returns true, preserving their order.
+    pub fn retain(&mut self, mut pred: impl FnMut(&T) -> bool) {
+        self.retain_mut(|value| pred(value));
+    }
+    /// Keeps only the elements for which `pred` returns true, preserving their order.
+    /// Unlike [Self::retain], `pred` may modify the elements.
+    /// If `pred` panics, the elements it has not rejected yet are kept.
+    pub fn retain_mut(&mut self, mut pred: impl FnMut(&mut T) -> bool) {
+        let mut guard = CompactGuard::new(self, 0);
+        while guard.read < guard.len {
             unsafe {
-                if !pred(guard.arr.get_unchecked_mut(guard.read)) {
-                    guard.arr.idx_to_ptr(guard.read).drop_in_place();
-                    guard.read += 1;
+                let current = guard.arr.idx_to_ptr(guard.read);
+                if pred(&mut *current) {
+                    guard.keep_current();
                     continue;
                 }
-                guard
-                    .arr
-                    .idx_to_ptr(guard.write)
-                    .copy_from(guard.arr.idx_to_ptr(guard.read), 1);
-                guard.write += 1;
+                // Advance first, so a panicking Drop impl can't cause a double drop.
+                guard.read += 1;
+                current.drop_in_place();
             }
-            guard.read += 1;
         }
-        guard.arr.len = guard.write;
+    }
+    /// Keeps only the elements for which `keep` returns true, preserving their order.
+    /// Every discarded element is passed to `on_remove` instead of being dropped.
+    pub fn retain_with_removed(
+        &mut self,
+        mut keep: impl FnMut(&mut T) -> bool,
+        mut on_remove: impl FnMut(T),
+    ) {
+        let mut guard = CompactGuard::new(self, 0);
+        while guard.read < guard.len {
+            unsafe {
+                let current = guard.arr.idx_to_ptr(guard.read);
+                if keep(&mut *current) {
+                    guard.keep_current();
+                    continue;
+                }
+                // Advance first, so the closure owns the element even if it panics.
+                guard.read += 1;
+                on_remove(current.read());
+            }
+        }
+    }
+    /// Consumes the array, moving the elements for which `pred` returns true into the first
+    /// array and the rest into the second, preserving their order.
+    pub fn partition(self, mut pred: impl FnMut(&T) -> bool) -> (Array<T>, Array<T>) {
+        let mut matching = Array::new();
+        let mut rest = Array::new();
+        // If pred panics, dropping the iterator drops the remaining elements.
+        for value in self {
+            if pred(&value) {
+                matching.push(value);
+            } else {
+                rest.push(value);
+            }
+        }
+        (matching, rest)
+    }
+    /// Removes consecutive elements for which `same(current, previous)` returns true, keeping
+    /// the first element of every run.
+    pub fn dedup_by(&mut self, mut same: impl FnMut(&mut T, &mut T) -> bool) {
+        if self.len <= 1 {
+            return;
+        }
+        let mut guard = CompactGuard::new(self, 1);
+        while guard.read < guard.len {
+            unsafe {
+                let current = guard.arr.idx_to_ptr(guard.read);
+                let previous = guard.arr.idx_to_ptr(guard.write - 1);
+                if !same(&mut *current, &mut *previous) {
+                    guard.keep_current();
+                    continue;
+                }
+                guard.read += 1;
+                current.drop_in_place();
+            }
+        }
+    }
+    /// Removes consecutive elements that map to the same key, keeping the first element of every
+    /// run.
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&mut T) -> K) {
+        self.dedup_by(|current, previous| key(current) == key(previous));
+    }
+    /// Rotates the array in place so that the element at `mid` becomes the first, returning the
+    /// new index of the element that was at `cursor`.
+    /// # Panics
+    /// Panics if `mid > self.len()` or `cursor >= self.len()`.
+    #[track_caller]
+    pub fn rotate_left_tracking(&mut self, mid: usize, cursor: usize) -> usize {
+        if cursor >= self.len {
+            panic!(
+                "Cursor {cursor} would be out of bounds for Array of length {len}",
+                len = self.len
+            );
+        }
+        self.rotate_left(mid);
+        (cursor + self.len - mid) % self.len
+    }
+    /// Rotates the array in place so that the last `k` elements come first, returning the new
+    /// index of the element that was at `cursor`.
+    /// # Panics
+    /// Panics if `k > self.len()` or `cursor >= self.len()`.
+    #[track_caller]
+    pub fn rotate_right_tracking(&mut self, k: usize, cursor: usize) -> usize {
+        if cursor >= self.len {
+            panic!(
+                "Cursor {cursor} would be out of bounds for Array of length {len}",
+                len = self.len
+            );
+        }
+        self.rotate_right(k);
+        (cursor + k) % self.len
+    }
+    /// Overwrites every element with the result of calling `f`, dropping the old elements.
+    pub fn fill_with(&mut self, f: impl FnMut() -> T) {
+        (**self).fill_with(f);
+    }
+    /// Sorts the array by the key `f` returns, calling `f` only once per element.
+    /// The sort is stable. The keys are stored in a temporary [Array] instead of a [Vec].
+    pub fn sort_by_cached_key<K: Ord>(&mut self, mut f: impl FnMut(&T) -> K) {
+        if self.len < 2 {
+            return;
+        }
+        // Pairing every key with its index makes the unstable sort stable.
+        let mut keys: Array<(K, usize)> = self.iter().map(&mut f).zip(0..).collect();
+        keys.sort_unstable();
+        // Apply the permutation in place. Earlier slots have already been swapped away, so
+        // follow the chain of swaps to find where the element currently is.
+        for i in 0..self.len {
+            let mut idx = keys[i].1;
+            while idx < i {
+                idx = keys[idx].1;
+            }
+            keys[i].1 = idx;
+            self.swap(i, idx);
+        }
     }
 }
 
-impl<T: Debug, A: Allocator> Debug for Array<T, A> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+impl<T: PartialEq, A: Allocator> Array<T, A> {
+    /// Returns true if the array contains an element equal to `x`.
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool {
+        (**self).contains(x)
+    }
+    /// Removes consecutive repeated elements, keeping the first element of every run.
+    pub fn dedup(&mut self) {
+        self.dedup_by(|current, previous| current == previous);
+    }
+    /// Removes consecutive repeated elements like [Self::dedup], returning how many were removed.
+    pub fn dedup_count(&mut self) -> usize {
+        let old_len = self.len;
+        self.dedup();
+        old_len - self.len
     }
 }
 
-impl<T, A: Allocator> Extend<T> for Array<T, A> {
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for value in iter {
-            self.push(value);
-        }
+impl<T: Ord, A: Allocator> Array<T, A> {
+    /// Inserts `value` into a sorted array, keeping it sorted, and returns the index it was
+    /// inserted at.
+    /// See [Self::insert_sorted_by].
+    pub fn insert_sorted(&mut self, value: T) -> usize {
+        self.insert_sorted_by(value, T::cmp)
     }
 }
 
-impl<T> FromIterator<T> for Array<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut arr = Array::new();
-        arr.extend(iter);
-        arr
+impl<T: Clone, A: Allocator> Array<T, A> {
+    /// Creates a new array holding `n` copies of this array's elements, allocating only once.
+    /// # Panics
+    /// Panics with "capacity overflow" if the resulting length would overflow.
+    pub fn repeat(&self, n: usize) -> Array<T> {
+        let Some(len) = self.len.checked_mul(n) else {
+            panic!("capacity overflow");
+        };
+        let mut out = Array::with_capacity(len);
+        for _ in 0..n {
+            out.extend_from_slice(self);
+        }
+        out
+    }
+    /// Overwrites every element with a clone of `value`, dropping the old elements.
+    /// The last element receives `value` itself.
+    pub fn fill(&mut self, value: T) {
+        (**self).fill(value);
+    }
+    /// Overwrites every element with a clone of the matching element of `src`.
+    /// # Panics
+    /// Panics if `src.len() != self.len()`.
+    #[track_caller]
+    pub fn clone_from_slice(&mut self, src: &[T]) {
+        if src.len() != self.len {
+            panic!(
+                "Source slice of length {src_len} does not match Array of length {len}",
+                src_len = src.len(),
+                len = self.len
+            );
+        }
+        (**self).clone_from_slice(src);
+    }
+    /// Clones and appends all elements of `other` to the array, reallocating at most once.
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.reserve(other.len());
+        let base = self.idx_to_ptr(0);
+        let mut len = SetLenOnDrop::new(&mut self.len);
+        for value in other {
+            unsafe {
+                base.add(len.local_len).write(value.clone());
+            }
+            len.local_len += 1;
+        }
+    }
+    /// Clones the elements in `range` and appends them to the end of the array.
+    /// # Panics
+    /// Panics if `range` is out of bounds.
+    #[track_caller]
+    pub fn extend_from_within(&mut self, range: impl RangeBounds<usize>) {
+        let (start, end) = range_to_bounds(range, self.len);
+        if start > end || end > self.len {
+            panic!(
+                "Range {start}..{end} would be out of bounds for Array of length {len}",
+                len = self.len
+            );
+        }
+        self.reserve(end - start);
+        // Only read the source after reserving, as it may have moved the buffer.
+        let base = self.idx_to_ptr(0);
+        let mut len = SetLenOnDrop::new(&mut self.len);
+        for idx in start..end {
+            unsafe {
+                let value = (*base.add(idx)).clone();
+                base.add(len.local_len).write(value);
+            }
+            len.local_len += 1;
+        }
+    }
+    /// Resizes the array to `new_len`, either filling the new slots with clones of `value`, or
+    /// dropping the elements past `new_len`.
+    /// The last new slot receives `value` itself, avoiding one unnecessary clone.
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+        self.reserve(new_len - self.len);
+        let base = self.idx_to_ptr(0);
+        let mut len = SetLenOnDrop::new(&mut self.len);
+        while len.local_len < new_len - 1 {
+            unsafe {
+                base.add(len.local_len).write(value.clone());
+            }
+            len.local_len += 1;
+        }
+        unsafe {
+            base.add(len.local_len).write(value);
+        }
+        len.local_len += 1;
     }
 }
 
-impl<T, A: Allocator> Deref for Array<T, A> {
-    type Target = [T];
-
-    fn deref(&self) -> &Self::Target {
-        unsafe { self.value_slice().as_ref() }
+impl<T: Copy, A: Allocator> Array<T, A> {
+    /// Overwrites every element with a copy of the matching element of `src`.
+    /// # Panics
+    /// Panics if `src.len() != self.len()`.
+    #[track_caller]
+    pub fn copy_from_slice(&mut self, src: &[T]) {
+        if src.len() != self.len {
+            panic!(
+                "Source slice of length {src_len} does not match Array of length {len}",
+                src_len = src.len(),
+                len = self.len
+            );
+        }
+        (**self).copy_from_slice(src);
     }
 }
 
-impl<T, A: Allocator> DerefMut for Array<T, A> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { self.value_slice().as_mut() }
+impl<T, A: Allocator + Clone> Array<T, A> {
+    /// Splits the array in two at `at`, returning a new array holding the elements `at..`, and
+    /// leaving `self` with the elements `0..at`.
+    /// # Panics
+    /// Panics if `at > self.len()`.
+    #[track_caller]
+    pub fn split_off(&mut self, at: usize) -> Array<T, A> {
+        if at > self.len {
+            panic!(
+                "Split index {at} would be out of bounds for Array of length {len}",
+                len = self.len
+            );
+        }
+        let count = self.len - at;
+        let mut other = Array::with_capacity_in(count, self.alloc.clone());
+        unsafe {
+            self.idx_to_ptr(at)
+                .copy_to_nonoverlapping(other.idx_to_ptr(0), count);
+            self.len = at;
+            other.len = count;
+        }
+        other
     }
 }
 
-impl<T> Default for Array<T> {
-    fn default() -> Self {
-        Self::new()
+impl<T, A: Allocator, A2: Allocator> Array<Array<T, A>, A2> {
+    /// Moves the elements of all inner arrays into a single array, allocating only once.
+    /// The inner buffers are freed without dropping their elements.
+    pub fn flatten(self) -> Array<T> {
+        let len = self.total_len();
+        let mut out = Array::with_capacity(len);
+        for mut arr in self {
+            unsafe {
+                arr.as_ptr()
+                    .copy_to_nonoverlapping(out.idx_to_ptr(out.len), arr.len);
+                out.len += arr.len;
+                // The elements are owned by `out` now, dropping `arr` only frees its buffer.
+                arr.set_len(0);
+            }
+        }
+        out
+    }
+    /// Returns the total length of the inner arrays.
+    /// # Panics
+    /// Panics with "capacity overflow" if the total length doesn't fit in a `usize`.
+    fn total_len(&self) -> usize {
+        let len = self
+            .iter()
+            .try_fold(0, |sum: usize, arr| sum.checked_add(arr.len()));
+        let Some(len) = len else {
+            panic!("capacity overflow");
+        };
+        len
     }
 }
 
-impl<T, A: Allocator> Drop for Array<T, A> {
+impl<T: Clone, A: Allocator, A2: Allocator> Array<Array<T, A>, A2> {
+    /// Clones the elements of all inner arrays into a single array, allocating only once.
+    pub fn concat(&self) -> Array<T> {
+        let len = self.total_len();
+        let mut out = Array::with_capacity(len);
+        for arr in self.iter() {
+            out.extend_from_slice(arr);
+        }
+        out
+    }
+    /// Clones the elements of all inner arrays into a single array with `sep` between each of
+    /// them, allocating only once.
+    pub fn join(&self, sep: &[T]) -> Array<T> {
+        let Some((first, rest)) = self.split_first() else {
+            return Array::new();
+        };
+        let len = sep
+            .len()
+            .checked_mul(rest.len())
+            .and_then(|seps| seps.checked_add(self.total_len()));
+        let Some(len) = len else {
+            panic!("capacity overflow");
+        };
+        let mut out = Array::with_capacity(len);
+        out.extend_from_slice(first);
+        for arr in rest {
+            out.extend_from_slice(sep);
+            out.extend_from_slice(arr);
+        }
+        out
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for Array<T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, A: Allocator, A2: Allocator> PartialEq<Array<T, A2>> for Array<T, A> {
+    fn eq(&self, other: &Array<T, A2>) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq<[T]> for Array<T, A> {
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq, A: Allocator> PartialEq<&[T]> for Array<T, A> {
+    fn eq(&self, other: &&[T]) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, A: Allocator, const N: usize> PartialEq<[T; N]> for Array<T, A> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: Eq, A: Allocator> Eq for Array<T, A> {}
+
+impl<T: PartialOrd, A: Allocator> PartialOrd for Array<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Ord, A: Allocator> Ord for Array<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: Hash, A: Allocator> Hash for Array<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for Array<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, A: Allocator> Extend<&'a T> for Array<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, A: Allocator + Default> FromIterator<T> for Array<T, A> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Array::from_iter_in(iter, A::default())
+    }
+}
+
+impl<T, A: Allocator> Deref for Array<T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.value_slice().as_ref() }
+    }
+}
+
+impl<T, A: Allocator> DerefMut for Array<T, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.value_slice().as_mut() }
+    }
+}
+
+/// Panics with the message for an invalid `index` into an Array of length `len`, where `O` is the
+/// output type of the index.
+/// Only range indices produce a subslice, and only they can be reversed. They are told apart from
+/// scalar indices by the pointer to their output being wide.
+#[track_caller]
+fn index_failed<O: ?Sized>(index: impl Debug, len: usize) -> ! {
+    if size_of::<*const O>() == size_of::<*const ()>() {
+        panic!("Index {index:?} would be out of bounds for Array of length {len}");
+    }
+    panic!("Range {index:?} would be out of bounds or reversed for Array of length {len}");
+}
+
+/// Unlike slice indexing, `I` must also implement [Clone] and [Debug], so that the panic message
+/// can include the index.
+impl<T, A: Allocator, I: SliceIndex<[T]> + Clone + Debug> Index<I> for Array<T, A> {
+    type Output = I::Output;
+
+    #[track_caller]
+    fn index(&self, index: I) -> &Self::Output {
+        match (**self).get(index.clone()) {
+            Some(value) => value,
+            None => index_failed::<I::Output>(index, self.len),
+        }
+    }
+}
+
+/// See the [Index] impl for the extra bounds on `I`.
+impl<T, A: Allocator, I: SliceIndex<[T]> + Clone + Debug> IndexMut<I> for Array<T, A> {
+    #[track_caller]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let len = self.len;
+        match (**self).get_mut(index.clone()) {
+            Some(value) => value,
+            None => index_failed::<I::Output>(index, len),
+        }
+    }
+}
+
+impl<T, A: Allocator> AsRef<[T]> for Array<T, A> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> AsMut<[T]> for Array<T, A> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> Borrow<[T]> for Array<T, A> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A: Allocator> BorrowMut<[T]> for Array<T, A> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Array<T> {
+    fn from(values: [T; N]) -> Self {
+        let mut arr = Array::with_capacity(N);
+        let values = ManuallyDrop::new(values);
+        unsafe {
+            values.as_ptr().copy_to_nonoverlapping(arr.idx_to_ptr(0), N);
+            arr.set_len(N);
+        }
+        arr
+    }
+}
+
+impl<T: Clone> From<&[T]> for Array<T> {
+    fn from(values: &[T]) -> Self {
+        let mut arr = Array::with_capacity(values.len());
+        arr.extend_from_slice(values);
+        arr
+    }
+}
+
+/// Reuses the allocation of the [Vec], without copying any elements.
+impl<T> From<Vec<T>> for Array<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        // Array and Vec both allocate their buffers as Layout::array::<T>(capacity) in Global.
+        Array {
+            len: vec.len(),
+            cap: vec.capacity(),
+            buf: unsafe { NonNull::new_unchecked(vec.as_mut_ptr().cast()) },
+            alloc: Global,
+        }
+    }
+}
+
+/// Reuses the allocation of the [Array], without copying any elements.
+impl<T> From<Array<T>> for Vec<T> {
+    fn from(arr: Array<T>) -> Self {
+        let arr = ManuallyDrop::new(arr);
+        unsafe { Vec::from_raw_parts(arr.idx_to_ptr(0), arr.len, arr.cap) }
+    }
+}
+
+impl<T, A: Allocator, const N: usize> TryFrom<Array<T, A>> for [T; N] {
+    type Error = Array<T, A>;
+
+    /// Moves the elements out into a fixed-size array, returning the [Array] back if its length
+    /// is not exactly `N`.
+    fn try_from(mut arr: Array<T, A>) -> Result<Self, Self::Error> {
+        if arr.len() != N {
+            return Err(arr);
+        }
+        unsafe {
+            // The elements are moved out, the buffer is freed when arr is dropped.
+            arr.set_len(0);
+            Ok(arr.idx_to_ptr(0).cast::<[T; N]>().read())
+        }
+    }
+}
+
+impl<T, A: Allocator + Default> Default for Array<T, A> {
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl<T, A: Allocator> Drop for Array<T, A> {
     fn drop(&mut self) {
         let arr = core::mem::ManuallyDrop::new(self);
         unsafe {
@@ -279,3 +1397,1278 @@ impl<T, A: Allocator> Drop for Array<T, A> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{alloc::AllocError, cell::Cell, rc::Rc};
+
+    /// Counts how many times it was dropped in a shared counter.
+    pub(super) struct Tracked(pub(super) usize, pub(super) Rc<Cell<usize>>);
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+    pub(super) fn tracked(
+        range: std::ops::Range<usize>,
+        drops: &Rc<Cell<usize>>,
+    ) -> Array<Tracked> {
+        range.map(|i| Tracked(i, drops.clone())).collect()
+    }
+    pub(super) fn values(arr: &[Tracked]) -> Vec<usize> {
+        arr.iter().map(|t| t.0).collect()
+    }
+
+    /// Counts its clones in a shared counter, panicking instead of making clone number
+    /// `panic_at`. Drops are tracked by the inner [Tracked].
+    pub(super) struct Cloning {
+        pub(super) tracked: Tracked,
+        pub(super) clones: Rc<Cell<usize>>,
+        pub(super) panic_at: usize,
+    }
+    impl Cloning {
+        pub(super) fn new(id: usize, drops: &Rc<Cell<usize>>, clones: &Rc<Cell<usize>>) -> Self {
+            Cloning {
+                tracked: Tracked(id, drops.clone()),
+                clones: clones.clone(),
+                panic_at: usize::MAX,
+            }
+        }
+    }
+    impl Clone for Cloning {
+        fn clone(&self) -> Self {
+            let count = self.clones.get() + 1;
+            if count == self.panic_at {
+                panic!("clone");
+            }
+            self.clones.set(count);
+            Cloning {
+                tracked: Tracked(self.tracked.0, self.tracked.1.clone()),
+                clones: self.clones.clone(),
+                panic_at: self.panic_at,
+            }
+        }
+    }
+
+    /// An allocator that never hands out memory.
+    struct Failing;
+    unsafe impl Allocator for Failing {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+    }
+
+    /// A [Global]-backed allocator carrying a tag, to tell allocator instances apart.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub(super) struct Tagged(pub(super) u32);
+    unsafe impl Allocator for Tagged {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global.allocate(layout)
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    const fn assert_send<T: Send>() {}
+    const _: () = assert_send::<Array<i32, Tagged>>();
+
+    #[test]
+    fn try_reserve_failing_allocator() {
+        let mut arr: Array<u32, Failing> = Array::new_in(Failing);
+        assert!(matches!(
+            arr.try_reserve(4),
+            Err(TryReserveError::AllocError { .. })
+        ));
+        assert!(matches!(
+            arr.try_reserve_exact(4),
+            Err(TryReserveError::AllocError { .. })
+        ));
+        assert_eq!(arr.capacity(), 0);
+        assert_eq!(arr.push_within_capacity(1), Err(1));
+        assert!(arr.is_empty());
+    }
+
+    #[test]
+    fn try_reserve_overflow_leaves_array_unchanged() {
+        let mut arr: Array<u32> = (0..3).collect();
+        let cap = arr.capacity();
+        assert_eq!(
+            arr.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(
+            arr.try_reserve_exact(usize::MAX / 2),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(arr.capacity(), cap);
+        assert_eq!(arr, [0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_overflow() {
+        let mut arr: Array<u32> = (0..3).collect();
+        arr.reserve(usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_exact_overflow() {
+        let mut arr: Array<u32> = (0..3).collect();
+        arr.reserve_exact(usize::MAX / 4);
+    }
+
+    #[test]
+    fn zst_push_up_to_max_len() {
+        let mut arr: Array<()> = Array::new();
+        unsafe { arr.set_len(usize::MAX - 1) };
+        arr.push(());
+        assert_eq!(arr.len(), usize::MAX);
+        assert_eq!(arr.pop(), Some(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn zst_push_past_max_len() {
+        let mut arr: Array<()> = Array::new();
+        unsafe { arr.set_len(usize::MAX) };
+        arr.push(());
+    }
+
+    pub(super) fn strings(range: std::ops::Range<usize>) -> Array<String> {
+        range.map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn insert_front_into_full_array() {
+        let mut arr: Array<String> = Array::new();
+        arr.reserve_exact(4);
+        arr.extend(strings(0..4));
+        assert_eq!(arr.len(), arr.capacity());
+        // Shifting the elements must not touch memory past the old buffer, which Miri checks.
+        arr.insert(0, "x".into());
+        assert_eq!(arr, ["x", "0", "1", "2", "3"].map(String::from));
+        arr.insert(5, "y".into());
+        assert_eq!(arr.last().unwrap(), "y");
+    }
+
+    #[test]
+    fn swap_remove_last() {
+        let mut arr = strings(0..4);
+        // The last index must not copy the element onto itself.
+        assert_eq!(arr.swap_remove(3).unwrap(), "3");
+        assert_eq!(arr.swap_remove(0).unwrap(), "0");
+        assert_eq!(arr, ["2", "1"].map(String::from));
+        assert!(arr.swap_remove(2).is_none());
+    }
+
+    #[test]
+    fn drain_rev() {
+        let mut arr = strings(0..8);
+        let drained: Vec<String> = arr.drain(2..6).rev().collect();
+        assert_eq!(drained, ["5", "4", "3", "2"].map(String::from));
+        assert_eq!(arr, ["0", "1", "6", "7"].map(String::from));
+    }
+
+    #[test]
+    fn into_iter_nth_back_matches_vec() {
+        for n in 0..8 {
+            let mut arr = strings(0..5).into_iter();
+            let mut vec = Vec::from(strings(0..5)).into_iter();
+            assert_eq!(arr.nth_back(n), vec.nth_back(n), "nth_back({n})");
+            assert_eq!(arr.collect::<Vec<_>>(), vec.collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn retain_with_removed() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..10, &drops);
+        let mut removed = Vec::new();
+        arr.retain_with_removed(|t| t.0 % 3 != 0, |t| removed.push(t));
+        assert_eq!(values(&arr), [1, 2, 4, 5, 7, 8]);
+        assert_eq!(values(&removed), [0, 3, 6, 9]);
+        assert_eq!(drops.get(), 0);
+    }
+
+    #[test]
+    fn retain_with_removed_panic_keeps_unvisited() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..8, &drops);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.retain_with_removed(
+                |t| t.0 % 2 == 1,
+                |t| {
+                    if t.0 == 4 {
+                        panic!("on_remove");
+                    }
+                },
+            );
+        }));
+        assert!(result.is_err());
+        // 0, 2 and 4 were handed to on_remove, which dropped them.
+        assert_eq!(drops.get(), 3);
+        assert_eq!(values(&arr), [1, 3, 5, 6, 7]);
+    }
+
+    #[test]
+    fn dedup_by_panic_keeps_unvisited() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr: Array<Tracked> = [0, 0, 1, 1, 2, 2]
+            .into_iter()
+            .map(|n| Tracked(n, drops.clone()))
+            .collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.dedup_by(|current, previous| {
+                if current.0 == 2 {
+                    panic!("same");
+                }
+                current.0 == previous.0
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+        assert_eq!(values(&arr), [0, 1, 2, 2]);
+    }
+
+    /// Maps every element to a [Tracked] with the doubled value, counting drops in `mapped`.
+    /// Panics on `panic_at`, and fails on `fail_at`.
+    fn double(
+        t: Tracked,
+        mapped: &Rc<Cell<usize>>,
+        panic_at: usize,
+        fail_at: usize,
+    ) -> Result<Tracked, usize> {
+        match t.0 {
+            n if n == panic_at => panic!("map"),
+            n if n == fail_at => Err(n),
+            n => Ok(Tracked(n * 2, mapped.clone())),
+        }
+    }
+
+    #[test]
+    fn map_same_size_reuses_buffer() {
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let arr = tracked(0..5, &drops);
+        let ptr = arr.as_ptr();
+        let arr = arr.map(|t| double(t, &mapped, usize::MAX, usize::MAX).unwrap());
+        assert_eq!(arr.as_ptr(), ptr);
+        assert_eq!(values(&arr), [0, 2, 4, 6, 8]);
+        assert_eq!((drops.get(), mapped.get()), (5, 0));
+        drop(arr);
+        assert_eq!(mapped.get(), 5);
+    }
+
+    #[test]
+    fn map_different_size() {
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let arr = tracked(0..5, &drops).map(|t| (double(t, &mapped, usize::MAX, usize::MAX), 0u8));
+        assert_eq!(arr.len(), 5);
+        assert_eq!((drops.get(), mapped.get()), (5, 0));
+        drop(arr);
+        assert_eq!(mapped.get(), 5);
+    }
+
+    #[test]
+    fn map_panic_drops_everything_once() {
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let arr = tracked(0..5, &drops);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.map(|t| double(t, &mapped, 2, usize::MAX).unwrap())
+        }));
+        assert!(result.is_err());
+        assert_eq!((drops.get(), mapped.get()), (5, 2));
+
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let arr = tracked(0..5, &drops);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.map(|t| (double(t, &mapped, 2, usize::MAX).unwrap(), 0u8))
+        }));
+        assert!(result.is_err());
+        assert_eq!((drops.get(), mapped.get()), (5, 2));
+    }
+
+    #[test]
+    fn try_map_third_of_five_fails() {
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let result = tracked(0..5, &drops).try_map(|t| double(t, &mapped, usize::MAX, 2));
+        assert_eq!(result.err(), Some(2));
+        assert_eq!((drops.get(), mapped.get()), (5, 2));
+
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let result =
+            tracked(0..5, &drops).try_map(|t| double(t, &mapped, usize::MAX, 2).map(|t| (t, 0u8)));
+        assert_eq!(result.err(), Some(2));
+        assert_eq!((drops.get(), mapped.get()), (5, 2));
+    }
+
+    #[test]
+    fn try_map_success() {
+        let (drops, mapped) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let arr = tracked(0..5, &drops).try_map(|t| double(t, &mapped, usize::MAX, usize::MAX));
+        assert_eq!(values(&arr.unwrap()), [0, 2, 4, 6, 8]);
+        assert_eq!((drops.get(), mapped.get()), (5, 5));
+    }
+
+    /// Returns `count` arrays of zero-sized elements, each of length `usize::MAX`.
+    fn max_len_zsts(count: usize) -> Array<Array<()>> {
+        (0..count)
+            .map(|_| {
+                let mut arr = Array::new();
+                unsafe { arr.set_len(usize::MAX) };
+                arr
+            })
+            .collect()
+    }
+
+    #[test]
+    fn concat() {
+        let nested: Array<Array<String>> = [strings(0..2), Array::new(), strings(2..5)].into();
+        assert_eq!(nested.concat(), strings(0..5));
+        assert!(Array::<Array<String>>::new().concat().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn concat_len_overflow() {
+        max_len_zsts(2).concat();
+    }
+
+    #[test]
+    fn join() {
+        let nested: Array<Array<String>> = [strings(0..2), Array::new(), strings(2..3)].into();
+        let sep = strings(7..9);
+        assert_eq!(nested.join(&sep)[..], ["0", "1", "7", "8", "7", "8", "2"]);
+        assert!(Array::<Array<String>>::new().join(&sep).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn join_separator_len_overflow() {
+        let nested: Array<Array<()>> = [Array::new(), Array::new(), Array::new()].into();
+        let sep = max_len_zsts(1).pop().unwrap();
+        nested.join(&sep);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn join_len_overflow() {
+        let mut nested = max_len_zsts(1);
+        nested.push(Array::new());
+        nested.join(&[()]);
+    }
+
+    #[test]
+    fn flatten_moves_elements() {
+        let drops = Rc::new(Cell::new(0));
+        let nested: Array<Array<Tracked>> = [
+            tracked(0..3, &drops),
+            Array::new(),
+            tracked(3..4, &drops),
+            tracked(4..7, &drops),
+        ]
+        .into();
+        let flat = nested.flatten();
+        assert_eq!(drops.get(), 0);
+        assert_eq!(values(&flat), [0, 1, 2, 3, 4, 5, 6]);
+        drop(flat);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn flatten_len_overflow() {
+        max_len_zsts(2).flatten();
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 5 would be out of bounds for Array of length 5")]
+    fn index_usize_out_of_bounds() {
+        _ = &strings(0..5)[5];
+    }
+
+    #[test]
+    #[should_panic(expected = "Range 2..7 would be out of bounds or reversed")]
+    fn index_range_out_of_bounds() {
+        _ = &strings(0..5)[2..7];
+    }
+
+    #[test]
+    #[should_panic(expected = "Range 3..1 would be out of bounds or reversed")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn index_range_reversed() {
+        _ = &strings(0..5)[3..1];
+    }
+
+    #[test]
+    #[should_panic(expected = "Range 6.. would be out of bounds or reversed")]
+    fn index_mut_range_out_of_bounds() {
+        strings(0..5)[6..].fill(String::new());
+    }
+
+    #[test]
+    fn retain_panic_keeps_unvisited() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..6, &drops);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.retain(|t| match t.0 {
+                3 => panic!("predicate"),
+                n => n % 2 == 0,
+            });
+        }));
+        assert!(result.is_err());
+        // Only 1 was rejected before the panic, the unvisited elements are kept like Vec does.
+        assert_eq!(drops.get(), 1);
+        assert_eq!(values(&arr), [0, 2, 3, 4, 5]);
+        drop(arr);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn retain() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..10, &drops);
+        arr.retain(|t| t.0 % 3 == 0);
+        assert_eq!(values(&arr), [0, 3, 6, 9]);
+        assert_eq!(drops.get(), 6);
+        arr.retain(|_| true);
+        assert_eq!(values(&arr), [0, 3, 6, 9]);
+        arr.retain(|_| false);
+        assert!(arr.is_empty());
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn retain_mut() {
+        let mut arr = strings(0..6);
+        arr.retain_mut(|s| {
+            s.push('!');
+            s != "2!"
+        });
+        assert_eq!(arr[..], ["0!", "1!", "3!", "4!", "5!"]);
+    }
+
+    #[test]
+    fn remove() {
+        let mut arr = strings(0..5);
+        assert_eq!(arr.remove(1), "1");
+        assert_eq!(arr.remove(3), "4");
+        assert_eq!(arr[..], ["0", "2", "3"]);
+        assert_eq!(arr.try_remove(3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 5 would be out of bounds for Array of length 5")]
+    fn remove_out_of_bounds() {
+        strings(0..5).remove(5);
+    }
+
+    #[test]
+    fn insert() {
+        let mut arr = strings(0..3);
+        arr.insert(1, "a".to_string());
+        arr.insert(4, "b".to_string());
+        arr.insert(0, "c".to_string());
+        assert_eq!(arr[..], ["c", "0", "a", "1", "2", "b"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 4 would be out of bounds for Array of length 3")]
+    fn insert_out_of_bounds() {
+        strings(0..3).insert(4, String::new());
+    }
+
+    #[test]
+    fn try_insert_returns_value() {
+        let mut arr = strings(0..3);
+        assert_eq!(arr.try_insert(4, "a".to_string()), Err("a".to_string()));
+        assert_eq!(arr.try_insert(3, "b".to_string()), Ok(()));
+        assert_eq!(arr[..], ["0", "1", "2", "b"]);
+    }
+
+    #[test]
+    fn split_off_drops_nothing() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..6, &drops);
+        let tail = arr.split_off(4);
+        assert_eq!(drops.get(), 0);
+        assert_eq!(values(&arr), [0, 1, 2, 3]);
+        assert_eq!(values(&tail), [4, 5]);
+        assert!(arr.split_off(4).is_empty());
+        let all = arr.split_off(0);
+        assert!(arr.is_empty());
+        assert_eq!(drops.get(), 0);
+        drop((all, tail));
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Split index 4 would be out of bounds for Array of length 3")]
+    fn split_off_out_of_bounds() {
+        strings(0..3).split_off(4);
+    }
+
+    #[test]
+    fn rotate_tracking() {
+        let mut arr = strings(0..5);
+        assert_eq!(arr.rotate_left_tracking(0, 3), 3);
+        assert_eq!(arr, strings(0..5));
+        assert_eq!(arr.rotate_left_tracking(5, 3), 3);
+        assert_eq!(arr, strings(0..5));
+        assert_eq!(arr.rotate_left_tracking(2, 1), 4);
+        assert_eq!(arr[..], ["2", "3", "4", "0", "1"]);
+        assert_eq!(arr[4], "1");
+
+        let mut arr = strings(0..5);
+        assert_eq!(arr.rotate_right_tracking(0, 3), 3);
+        assert_eq!(arr.rotate_right_tracking(5, 3), 3);
+        assert_eq!(arr, strings(0..5));
+        assert_eq!(arr.rotate_right_tracking(2, 4), 1);
+        assert_eq!(arr[..], ["3", "4", "0", "1", "2"]);
+        assert_eq!(arr[1], "4");
+    }
+
+    #[test]
+    #[should_panic(expected = "Cursor 5 would be out of bounds for Array of length 5")]
+    fn rotate_cursor_out_of_bounds() {
+        strings(0..5).rotate_left_tracking(1, 5);
+    }
+
+    #[test]
+    fn extend_from_within() {
+        let mut arr = strings(0..6);
+        arr.extend_from_within(..3);
+        assert_eq!(arr[..], ["0", "1", "2", "3", "4", "5", "0", "1", "2"]);
+        arr.extend_from_within(8..);
+        assert_eq!(arr.len(), 10);
+        assert_eq!(arr[9], "2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Range 2..7 would be out of bounds for Array of length 6")]
+    fn extend_from_within_out_of_bounds() {
+        strings(0..6).extend_from_within(2..7);
+    }
+
+    #[test]
+    fn zst_capacity() {
+        let mut arr: Array<()> = Array::new();
+        assert_eq!(arr.capacity(), usize::MAX);
+        arr.extend([(); 10]);
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), usize::MAX);
+        assert_eq!(Array::<()>::with_capacity(3).capacity(), usize::MAX);
+        assert_eq!(Array::<u8>::new().capacity(), 0);
+    }
+
+    #[test]
+    fn resize_with_panic() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = Array::new();
+        let mut calls = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.resize_with(5, || {
+                calls += 1;
+                if calls == 3 {
+                    panic!("f");
+                }
+                Tracked(calls, drops.clone())
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(values(&arr), [1, 2]);
+        assert_eq!(drops.get(), 0);
+        drop(arr);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn resize_clones_once_per_new_slot() {
+        let (drops, clones) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let mut arr: Array<Cloning> = (0..3).map(|n| Cloning::new(n, &drops, &clones)).collect();
+        arr.resize(7, Cloning::new(9, &drops, &clones));
+        // Four new copies of the value, the last of which is the value itself rather than a
+        // clone.
+        assert_eq!(arr.len(), 7);
+        assert!(arr[3..].iter().all(|c| c.tracked.0 == 9));
+        assert_eq!(clones.get(), 3);
+        assert_eq!(drops.get(), 0);
+        arr.resize(2, Cloning::new(9, &drops, &clones));
+        assert_eq!(clones.get(), 3);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn extend_from_slice_clone_panic() {
+        let (drops, clones) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let source: Array<Cloning> = (0..5)
+            .map(|n| Cloning {
+                panic_at: 3,
+                ..Cloning::new(n, &drops, &clones)
+            })
+            .collect();
+        let mut arr: Array<Cloning> = [Cloning::new(9, &drops, &clones)].into();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.extend_from_slice(&source);
+        }));
+        assert!(result.is_err());
+        // The two clones made before the panic are owned by the array.
+        let ids: Vec<_> = arr.iter().map(|c| c.tracked.0).collect();
+        assert_eq!(ids, [9, 0, 1]);
+        assert_eq!(drops.get(), 0);
+        drop(arr);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn amortized_growth() {
+        // Growing from a capacity that isn't a power of two keeps doubling it, instead of
+        // rounding up to the next power of two.
+        let mut arr: Array<u32> = Array::with_capacity(600);
+        let mut reallocations = 0;
+        for n in 0..1000 {
+            let cap = arr.capacity();
+            arr.push(n);
+            reallocations += usize::from(arr.capacity() != cap);
+        }
+        assert_eq!(reallocations, 1);
+        assert_eq!(arr.capacity(), 1200);
+        let collected: Array<u32> = (0..1000).collect();
+        assert_eq!(collected.capacity(), 1000);
+        // A large request jumps straight to the requested size.
+        arr.reserve(1 << 20);
+        assert_eq!(arr.capacity(), 1000 + (1 << 20));
+        assert!(arr.capacity() < arr.capacity().next_power_of_two() - (1 << 19));
+        // Small arrays start at the minimum capacity.
+        let mut arr: Array<u32> = Array::new();
+        arr.push(0);
+        assert_eq!(arr.capacity(), 16);
+    }
+
+    #[test]
+    fn vec_round_trip_reuses_allocation() {
+        let mut vec: Vec<String> = Vec::with_capacity(10);
+        vec.extend(["a", "b", "c"].map(String::from));
+        let ptr = vec.as_ptr();
+        let arr = Array::from(vec);
+        assert_eq!((arr.as_ptr(), arr.len(), arr.capacity()), (ptr, 3, 10));
+        assert_eq!(arr[..], ["a", "b", "c"]);
+        let vec = Vec::from(arr);
+        assert_eq!((vec.as_ptr(), vec.len(), vec.capacity()), (ptr, 3, 10));
+        assert_eq!(vec, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn vec_round_trip_empty_and_zst() {
+        let arr = Array::from(Vec::<String>::new());
+        assert_eq!((arr.len(), arr.capacity()), (0, 0));
+        assert!(Vec::from(arr).is_empty());
+        let arr = Array::from(vec![(); 5]);
+        assert_eq!((arr.len(), arr.capacity()), (5, usize::MAX));
+        assert_eq!(Vec::from(arr).len(), 5);
+        let vec = Vec::from(Array::<()>::new());
+        assert_eq!((vec.len(), vec.capacity()), (0, usize::MAX));
+    }
+
+    #[test]
+    fn raw_parts_round_trip() {
+        let mut arr = strings(0..3);
+        arr.reserve(10);
+        let (ptr, len, cap) = (arr.as_ptr(), arr.len(), arr.capacity());
+        let (raw, raw_len, raw_cap, alloc) = arr.into_raw_parts_with_alloc();
+        assert_eq!((raw.cast_const(), raw_len, raw_cap), (ptr, len, cap));
+        let arr = unsafe { Array::from_raw_parts_in(raw, raw_len, raw_cap, alloc) };
+        assert_eq!(arr, strings(0..3));
+        let (raw, raw_len, raw_cap) = arr.into_raw_parts();
+        let arr = unsafe { Array::from_raw_parts(raw, raw_len, raw_cap) };
+        assert_eq!((arr.as_ptr(), arr.capacity()), (ptr, cap));
+        assert_eq!(arr, strings(0..3));
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..5, &drops);
+        arr.reserve(20);
+        let boxed = arr.into_boxed_slice();
+        assert_eq!(boxed.len(), 5);
+        assert_eq!(values(&boxed), [0, 1, 2, 3, 4]);
+        assert_eq!(drops.get(), 0);
+        drop(boxed);
+        assert_eq!(drops.get(), 5);
+        assert!(Array::<String>::new().into_boxed_slice().is_empty());
+    }
+
+    #[test]
+    fn shrink_empty_then_push() {
+        // Shrinking to zero must leave the dangling sentinel behind, so the next push allocates
+        // a fresh buffer instead of growing a freed one. Run under Miri to check this.
+        let mut arr = strings(0..3);
+        arr.clear();
+        arr.shrink_to(0);
+        assert_eq!(arr.capacity(), 0);
+        arr.push("a".to_string());
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 1);
+        arr.pop();
+        arr.shrink_to_fit();
+        arr.extend(strings(0..5));
+        assert_eq!(arr, strings(0..5));
+    }
+
+    #[test]
+    fn clear_then_shrink_to_fit() {
+        let mut arr = strings(0..5);
+        arr.clear();
+        arr.shrink_to_fit();
+        assert_eq!(arr.capacity(), 0);
+    }
+
+    #[test]
+    fn shrink_to() {
+        let mut arr: Array<u64> = Array::with_capacity(16);
+        arr.extend([1, 2]);
+        assert_eq!(arr.capacity(), 16);
+        arr.shrink_to(8);
+        assert_eq!(arr.capacity(), 8);
+        arr.shrink_to(12);
+        assert_eq!(arr.capacity(), 8);
+        arr.shrink_to(0);
+        assert_eq!(arr.capacity(), 2);
+        assert_eq!(arr[..], [1, 2]);
+    }
+
+    #[test]
+    fn dedup_matches_vec() {
+        let inputs: [&[u32]; 6] = [
+            &[],
+            &[1],
+            // All equal and all distinct.
+            &[1; 6],
+            &[1, 2, 3, 4],
+            &[1, 1, 2, 3, 3, 3, 1, 4, 4],
+            &[5, 5, 6],
+        ];
+        for input in inputs {
+            let mut arr = Array::from(input);
+            let mut vec = input.to_vec();
+            arr.dedup();
+            vec.dedup();
+            assert_eq!(arr[..], vec[..]);
+        }
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut arr: Array<u32> = [10, 11, 20, 21, 22, 30, 11].into();
+        arr.dedup_by_key(|n| *n / 10);
+        assert_eq!(arr[..], [10, 20, 30, 11]);
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..6, &drops);
+        arr.dedup_by_key(|t| t.0 / 3);
+        assert_eq!(values(&arr), [0, 3]);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn partial_eq() {
+        let arr = strings(0..3);
+        let other_alloc: Array<String, Failing> = Array::new_in(Failing);
+        assert_eq!(arr, strings(0..3));
+        assert_ne!(arr, strings(0..4));
+        assert_eq!(arr, ["0", "1", "2"].map(String::from));
+        assert_eq!(arr, *["0", "1", "2"].map(String::from).as_slice());
+        assert_eq!(arr, &["0", "1", "2"].map(String::from)[..]);
+        assert_ne!(arr, other_alloc);
+        assert_eq!(Array::<String>::new(), other_alloc);
+    }
+
+    #[test]
+    fn ordering_is_lexicographic() {
+        let a: Array<u32> = [1, 2].into();
+        let b: Array<u32> = [1, 2, 3].into();
+        let c: Array<u32> = [1, 3].into();
+        let d: Array<u32> = [1, 2, 9].into();
+        assert!(a < b);
+        assert!(c > d);
+        assert_eq!(a.cmp(&Array::from([1, 2])), Ordering::Equal);
+        assert_eq!([&d, &a, &c, &b].into_iter().max(), Some(&c));
+        let nan: Array<f64> = [f64::NAN].into();
+        assert_eq!(nan.partial_cmp(&nan), None);
+    }
+
+    #[test]
+    fn hash_matches_slice() {
+        use std::{collections::HashSet, hash::BuildHasher};
+        let state = std::hash::RandomState::new();
+        let arr = strings(0..3);
+        assert_eq!(state.hash_one(&arr), state.hash_one(strings(0..3)));
+        assert_eq!(state.hash_one(&arr), state.hash_one(&arr[..]));
+        let set: HashSet<Array<String>> = [strings(0..3), strings(0..2), strings(0..3)].into();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&arr));
+        assert!(!set.contains(&strings(0..4)));
+    }
+
+    #[test]
+    fn borrow_as_slice_key() {
+        use std::collections::HashMap;
+        let mut map: HashMap<Array<u8>, usize> = HashMap::new();
+        map.insert(Array::from(&b"abc"[..]), 1);
+        map.insert(Array::from(&b"de"[..]), 2);
+        assert_eq!(map.get(&b"abc"[..]), Some(&1));
+        assert_eq!(map.get(&b"de"[..]), Some(&2));
+        assert_eq!(map.get(&b"ab"[..]), None);
+        let mut arr: Array<u8> = b"xyz".as_slice().into();
+        BorrowMut::<[u8]>::borrow_mut(&mut arr)[0] = b'X';
+        assert_eq!(AsRef::<[u8]>::as_ref(&arr), b"Xyz");
+    }
+
+    #[test]
+    fn from_fixed_array() {
+        let arr = Array::from([1, 2, 3]);
+        assert_eq!(arr.capacity(), 3);
+        assert_eq!(arr[..], [1, 2, 3]);
+        let drops = Rc::new(Cell::new(0));
+        let arr = Array::from([Tracked(0, drops.clone()), Tracked(1, drops.clone())]);
+        assert_eq!(drops.get(), 0);
+        drop(arr);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn try_into_fixed_array() {
+        let fixed: [String; 3] = strings(0..3).try_into().unwrap();
+        assert_eq!(fixed, ["0", "1", "2"]);
+        let err = <[String; 2]>::try_from(strings(0..3)).unwrap_err();
+        assert_eq!(err[..], ["0", "1", "2"]);
+        let err = <[String; 4]>::try_from(strings(0..3)).unwrap_err();
+        assert_eq!(err.len(), 3);
+    }
+
+    #[test]
+    fn from_slice_deep_copies() {
+        let source = [String::from("left"), String::from("right")];
+        let mut arr = Array::from(&source[..]);
+        assert_eq!(arr.capacity(), 2);
+        assert_eq!(arr[..], source);
+        assert_ne!(arr[0].as_ptr(), source[0].as_ptr());
+        arr[0].push('!');
+        assert_eq!(source[0], "left");
+        assert_eq!(arr[0], "left!");
+    }
+
+    #[test]
+    fn array_macro() {
+        let empty: Array<i32> = crate::array![];
+        assert!(empty.is_empty());
+        assert_eq!(empty.capacity(), 0);
+        let list = crate::array![1, 2, 3,];
+        assert_eq!(list[..], [1, 2, 3]);
+        assert_eq!(list.capacity(), 3);
+        let repeated = crate::array![String::from("x"); 3];
+        assert_eq!(repeated[..], ["x", "x", "x"]);
+        let none: Array<String> = crate::array![String::new(); 0];
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn leak() {
+        let mut arr = strings(0..3);
+        arr.reserve(10);
+        let leaked: &'static mut [String] = arr.leak();
+        leaked[1].push('!');
+        assert_eq!(leaked, ["0", "1!", "2"]);
+        // leak shrinks to fit, so the allocation can be reclaimed as a boxed slice of that length.
+        drop(unsafe { Box::from_raw(leaked as *mut [String]) });
+    }
+
+    #[test]
+    fn spare_capacity_then_set_len() {
+        let mut arr = strings(0..1);
+        arr.reserve(2);
+        let cap = arr.capacity();
+        let spare = arr.spare_capacity_mut();
+        assert_eq!(spare.len(), cap - 1);
+        spare[0].write(String::from("a"));
+        spare[1].write(String::from("b"));
+        unsafe { arr.set_len(arr.len() + 2) };
+        assert_eq!(arr[..], ["0", "a", "b"]);
+    }
+
+    #[test]
+    fn allocator_returns_the_instance() {
+        let mut arr = Array::new_in(Tagged(7));
+        arr.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(arr.allocator(), &Tagged(7));
+        let empty: Array<u8, Tagged> = Array::with_capacity_in(0, Tagged(3));
+        assert_eq!(empty.allocator(), &Tagged(3));
+    }
+
+    #[test]
+    fn collect_into_custom_allocator() {
+        let arr: Array<usize, Tagged> = (0..5).collect();
+        assert_eq!(arr[..], [0, 1, 2, 3, 4]);
+        assert_eq!(arr.allocator(), &Tagged::default());
+        let arr = Array::from_iter_in((0..3).map(|i| i * 2), Tagged(9));
+        assert_eq!(arr[..], [0, 2, 4]);
+        assert_eq!(arr.allocator(), &Tagged(9));
+    }
+
+    #[test]
+    fn extend_reserves_size_hint() {
+        let mut arr = Array::<u32>::new();
+        arr.extend(0..1000);
+        // A single reservation for the whole range, rather than doublings from MIN_CAP.
+        assert_eq!(arr.capacity(), 1000);
+        assert!(arr.iter().copied().eq(0..1000));
+        let mut arr = Array::<u32>::new();
+        arr.extend((0..1000).filter(|i| i % 2 == 0));
+        assert_eq!(arr.len(), 500);
+        assert!(arr.iter().copied().eq((0..1000).step_by(2)));
+    }
+
+    #[test]
+    fn get() {
+        let mut arr = strings(0..4);
+        assert_eq!(arr.get(1).unwrap(), "1");
+        assert_eq!(arr.get(4), None);
+        assert_eq!(arr.get(1..3).unwrap(), ["1", "2"]);
+        assert_eq!(arr.get(2..5), None);
+        assert_eq!(arr.get(4..).unwrap().len(), 0);
+        arr.get_mut(0).unwrap().push('!');
+        arr.get_mut(2..).unwrap().reverse();
+        assert_eq!(arr.get_mut(5), None);
+        assert_eq!(arr[..], ["0!", "1", "3", "2"]);
+    }
+
+    #[test]
+    fn first_and_last() {
+        let mut arr = Array::<i32>::new();
+        assert_eq!((arr.first(), arr.last()), (None, None));
+        assert_eq!(arr.first_mut(), None);
+        assert_eq!(arr.last_mut(), None);
+        arr.push(5);
+        assert_eq!((arr.first(), arr.last()), (Some(&5), Some(&5)));
+        *arr.first_mut().unwrap() += 1;
+        *arr.last_mut().unwrap() += 1;
+        assert_eq!(arr[..], [7]);
+        arr.extend([8, 9]);
+        assert_eq!((arr.first(), arr.last()), (Some(&7), Some(&9)));
+        *arr.first_mut().unwrap() = 0;
+        *arr.last_mut().unwrap() = 1;
+        assert_eq!(arr[..], [0, 8, 1]);
+    }
+
+    #[test]
+    fn contains() {
+        let arr = strings(0..3);
+        assert!(arr.contains(&String::from("2")));
+        assert!(!arr.contains(&String::from("3")));
+        assert!(!Array::<String>::new().contains(&String::new()));
+    }
+
+    #[test]
+    fn insert_sorted_positions() {
+        let mut arr = Array::new();
+        assert_eq!(arr.insert_sorted(5), 0);
+        assert_eq!(arr.insert_sorted(1), 0);
+        assert_eq!(arr.insert_sorted(9), 2);
+        assert_eq!(arr.insert_sorted(3), 1);
+        assert_eq!(arr[..], [1, 3, 5, 9]);
+        let mut arr: Array<(u32, &str)> = Array::new();
+        assert_eq!(arr.insert_sorted_by_key((20, "b"), |&(k, _)| k), 0);
+        assert_eq!(arr.insert_sorted_by_key((10, "a"), |&(k, _)| k), 0);
+        assert_eq!(arr.insert_sorted_by_key((40, "d"), |&(k, _)| k), 2);
+        assert_eq!(arr.insert_sorted_by_key((30, "c"), |&(k, _)| k), 2);
+        assert_eq!(arr[..], [(10, "a"), (20, "b"), (30, "c"), (40, "d")]);
+    }
+
+    #[test]
+    fn from_elem() {
+        let arr = Array::from_elem(String::from("x"), 0);
+        assert!(arr.is_empty());
+        assert_eq!(arr.capacity(), 0);
+        let arr = Array::from_elem(String::from("x"), 3);
+        assert_eq!(arr[..], ["x", "x", "x"]);
+        assert_eq!(arr.capacity(), 3);
+    }
+
+    #[test]
+    fn from_elem_clone_panic() {
+        let (drops, clones) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let mut value = Cloning::new(0, &drops, &clones);
+        value.panic_at = 3;
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Array::from_elem(value, 5)));
+        assert!(result.is_err());
+        // The two finished clones and the value itself, each dropped exactly once.
+        assert_eq!(clones.get(), 2);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn get_disjoint_mut() {
+        let mut arr = strings(0..4);
+        let [a, b] = arr.get_disjoint_mut([3, 0]).unwrap();
+        std::mem::swap(a, b);
+        assert_eq!(arr[..], ["3", "1", "2", "0"]);
+        assert!(arr.get_disjoint_mut([1, 2, 1]).is_none());
+        assert!(arr.get_disjoint_mut([0, 4]).is_none());
+        assert!(arr.get_disjoint_mut([]).is_some());
+    }
+
+    #[test]
+    fn split_at_spare_mut() {
+        let mut arr = strings(0..2);
+        arr.reserve(1);
+        let (init, spare) = arr.split_at_spare_mut();
+        spare[0].write(init[0].clone() + &init[1]);
+        init[1].push('!');
+        unsafe { arr.set_len(3) };
+        assert_eq!(arr[..], ["0", "1!", "01"]);
+    }
+
+    #[test]
+    fn extend_by_reference() {
+        let mut arr = Array::from([0]);
+        arr.extend([1, 2, 3].iter());
+        arr.extend(&[4]);
+        assert_eq!(arr[..], [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn sort_by_cached_key() {
+        let words = ["delta", "b", "ccc", "a", "eee", "dd", "f", "gg"];
+        let mut arr: Array<(&str, usize)> = words.iter().copied().zip(0..).collect();
+        let mut calls = 0;
+        arr.sort_by_cached_key(|&(word, _)| {
+            calls += 1;
+            word.len()
+        });
+        assert_eq!(calls, words.len());
+        let mut expected: Vec<_> = words.iter().copied().zip(0..).collect();
+        expected.sort_by_key(|&(word, _)| word.len());
+        // Equal keys keep their original relative order.
+        assert_eq!(arr[..], expected[..]);
+    }
+
+    #[test]
+    fn push_and_pop_front() {
+        let mut arr = Array::<String>::new();
+        assert_eq!(arr.pop_front(), None);
+        arr.push_front(String::from("b"));
+        assert_eq!(arr.pop_front().unwrap(), "b");
+        assert!(arr.is_empty());
+        assert_eq!(arr.pop_front(), None);
+        arr.push_front(String::from("c"));
+        arr.push_front(String::from("a"));
+        assert_eq!(arr[..], ["a", "c"]);
+        assert_eq!(arr.pop_front().unwrap(), "a");
+        assert_eq!(arr[..], ["c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Source slice of length 2 does not match Array of length 3")]
+    fn clone_from_slice_length_mismatch() {
+        strings(0..3).clone_from_slice(&[String::new(), String::new()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Source slice of length 4 does not match Array of length 3")]
+    fn copy_from_slice_length_mismatch() {
+        Array::from([1, 2, 3]).copy_from_slice(&[0; 4]);
+    }
+
+    #[test]
+    fn fill_drops_old_elements() {
+        let (drops, clones) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let mut arr: Array<Cloning> = (0..3).map(|n| Cloning::new(n, &drops, &clones)).collect();
+        arr.fill(Cloning::new(7, &drops, &clones));
+        // The last slot receives the value itself.
+        assert_eq!((drops.get(), clones.get()), (3, 2));
+        assert!(arr.iter().all(|c| c.tracked.0 == 7));
+        drop(arr);
+        assert_eq!(drops.get(), 6);
+
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..3, &drops);
+        let mut next = 10;
+        arr.fill_with(|| {
+            next += 1;
+            Tracked(next, drops.clone())
+        });
+        assert_eq!(drops.get(), 3);
+        assert_eq!(values(&arr), [11, 12, 13]);
+        drop(arr);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn join_single_inner_array() {
+        let single = Array::from([Array::from([1, 2, 3])]);
+        let joined = single.join(&[0, 0]);
+        assert_eq!(joined[..], [1, 2, 3]);
+        assert_eq!(joined.capacity(), 3);
+        assert_ne!(joined.as_ptr(), single[0].as_ptr());
+    }
+
+    #[test]
+    fn dedup_count() {
+        let mut arr = Array::from([1, 1, 2, 3, 3, 3]);
+        assert_eq!(arr.dedup_count(), 3);
+        assert_eq!(arr[..], [1, 2, 3]);
+        assert_eq!(arr.dedup_count(), 0);
+        assert_eq!(Array::<i32>::new().dedup_count(), 0);
+    }
+
+    #[test]
+    fn truncate_with_collects_removed() {
+        let mut arr = strings(0..5);
+        let mut removed = Vec::new();
+        arr.truncate_with(2, |s| removed.push(s));
+        assert_eq!(arr[..], ["0", "1"]);
+        assert_eq!(removed, ["2", "3", "4"]);
+        arr.truncate_with(2, |_| unreachable!());
+        assert_eq!(arr.len(), 2);
+    }
+
+    #[test]
+    fn truncate_with_panic_drops_rest() {
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = tracked(0..5, &drops);
+        let mut seen = Vec::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.truncate_with(1, |t| {
+                if t.0 == 2 {
+                    panic!("on_drop");
+                }
+                seen.push(t);
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(values(&arr), [0]);
+        assert_eq!(values(&seen), [1]);
+        // The element passed to the panicking call, and the two after it.
+        assert_eq!(drops.get(), 3);
+        drop((arr, seen));
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn truncate_panicking_drop() {
+        /// Panics while being dropped if its id is `panic_id`.
+        struct PanicOnDrop {
+            id: usize,
+            panic_id: usize,
+            drops: Rc<Cell<usize>>,
+        }
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+                if self.id == self.panic_id {
+                    panic!("drop");
+                }
+            }
+        }
+        let drops = Rc::new(Cell::new(0));
+        let mut arr: Array<PanicOnDrop> = (0..6)
+            .map(|id| PanicOnDrop {
+                id,
+                panic_id: 3,
+                drops: drops.clone(),
+            })
+            .collect();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| arr.truncate(1)));
+        assert!(result.is_err());
+        assert_eq!(arr.len(), 1);
+        // Every truncated element was dropped, including the ones after the panicking one.
+        assert_eq!(drops.get(), 5);
+        drop(arr);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn clear_keeps_allocation() {
+        let mut arr = strings(0..5);
+        let (cap, ptr) = (arr.capacity(), arr.as_ptr());
+        arr.clear();
+        assert!(arr.is_empty());
+        assert_eq!(arr.capacity(), cap);
+        arr.push(String::from("a"));
+        assert_eq!((arr.capacity(), arr.as_ptr()), (cap, ptr));
+    }
+
+    #[test]
+    fn append_empties_other_but_keeps_capacity() {
+        let mut arr = strings(0..2);
+        let mut other = strings(2..5);
+        let other_cap = other.capacity();
+        arr.append(&mut other);
+        assert_eq!(arr[..], ["0", "1", "2", "3", "4"]);
+        assert!(other.is_empty());
+        assert_eq!(other.capacity(), other_cap);
+        other.push(String::from("x"));
+        assert_eq!(other[..], ["x"]);
+    }
+
+    #[test]
+    fn pairs() {
+        let arr = Array::from([1, 2, 3, 4]);
+        let pairs: Vec<_> = arr.pairs().map(|(&a, &b)| (a, b)).collect();
+        assert_eq!(pairs, [(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(Array::from([1]).pairs().count(), 0);
+        assert_eq!(Array::<i32>::new().pairs().count(), 0);
+    }
+
+    #[test]
+    fn partition() {
+        let (evens, odds) = Array::from([0, 1, 2, 3, 4, 5, 6]).partition(|n| n % 2 == 0);
+        assert_eq!(evens[..], [0, 2, 4, 6]);
+        assert_eq!(odds[..], [1, 3, 5]);
+    }
+
+    #[test]
+    fn partition_panic_drops_everything_once() {
+        let drops = Rc::new(Cell::new(0));
+        let arr = tracked(0..5, &drops);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            arr.partition(|t| {
+                if t.0 == 2 {
+                    panic!("pred");
+                }
+                t.0 % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn repeat() {
+        let arr = Array::from([1, 2]);
+        let repeated = arr.repeat(3);
+        assert_eq!(repeated[..], [1, 2, 1, 2, 1, 2]);
+        assert_eq!(repeated.capacity(), 6);
+        let once = arr.repeat(1);
+        assert_eq!(once[..], [1, 2]);
+        let none = arr.repeat(0);
+        assert!(none.is_empty());
+        assert_eq!(none.capacity(), 0);
+    }
+
+    #[test]
+    fn with_capacity_never_reallocates() {
+        let mut arr = Array::with_capacity(50);
+        let (cap, ptr) = (arr.capacity(), arr.as_ptr());
+        assert_eq!(cap, 50);
+        for n in 0..50 {
+            arr.push(n);
+            assert_eq!((arr.capacity(), arr.as_ptr()), (cap, ptr));
+        }
+        assert_eq!(arr.len(), 50);
+    }
+}
@@ -0,0 +1,171 @@
+use super::Array;
+use ::rayon::{
+    iter::{
+        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+        IntoParallelRefMutIterator, ParallelIterator,
+        plumbing::{Consumer, Producer, ProducerCallback, UnindexedConsumer, bridge},
+    },
+    slice,
+};
+use std::alloc::Allocator;
+
+impl<'a, T: Sync + 'a, A: Allocator> IntoParallelIterator for &'a Array<T, A> {
+    type Iter = slice::Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        (**self).par_iter()
+    }
+}
+
+impl<'a, T: Send + 'a, A: Allocator> IntoParallelIterator for &'a mut Array<T, A> {
+    type Iter = slice::IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        (**self).par_iter_mut()
+    }
+}
+
+impl<T: Send, A: Allocator + Send> IntoParallelIterator for Array<T, A> {
+    type Iter = IntoParIter<T, A>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIter { arr: self }
+    }
+}
+
+/// A parallel iterator that moves the elements out of an [Array].
+pub struct IntoParIter<T, A: Allocator> {
+    arr: Array<T, A>,
+}
+
+impl<T: Send, A: Allocator + Send> ParallelIterator for IntoParIter<T, A> {
+    type Item = T;
+
+    fn drive_unindexed<C: UnindexedConsumer<T>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.arr.len())
+    }
+}
+
+impl<T: Send, A: Allocator + Send> IndexedParallelIterator for IntoParIter<T, A> {
+    fn drive<C: Consumer<T>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+    fn len(&self) -> usize {
+        self.arr.len()
+    }
+    fn with_producer<CB: ProducerCallback<T>>(mut self, callback: CB) -> CB::Output {
+        let len = self.arr.len();
+        unsafe {
+            // The producer takes ownership of the elements, the array only frees the buffer.
+            self.arr.set_len(0);
+            let slice = std::slice::from_raw_parts_mut(self.arr.idx_to_ptr(0), len);
+            callback.callback(DrainProducer { slice })
+        }
+    }
+}
+
+/// Hands out owned sub-slices of the array's buffer to rayon's workers.
+struct DrainProducer<'a, T> {
+    slice: &'a mut [T],
+}
+
+impl<'a, T: Send> Producer for DrainProducer<'a, T> {
+    type Item = T;
+    type IntoIter = SliceDrain<'a, T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let slice = std::mem::take(&mut self.slice);
+        SliceDrain {
+            iter: slice.iter_mut(),
+        }
+    }
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let (left, right) = std::mem::take(&mut self.slice).split_at_mut(index);
+        (
+            DrainProducer { slice: left },
+            DrainProducer { slice: right },
+        )
+    }
+}
+
+impl<'a, T> Drop for DrainProducer<'a, T> {
+    fn drop(&mut self) {
+        // Drop the elements of a producer that was never turned into an iterator.
+        let slice = std::mem::take(&mut self.slice);
+        unsafe { std::ptr::drop_in_place(slice) };
+    }
+}
+
+/// Moves the elements out of an owned sub-slice.
+struct SliceDrain<'a, T> {
+    iter: std::slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for SliceDrain<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        Some(unsafe { std::ptr::read(value) })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for SliceDrain<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next_back()?;
+        Some(unsafe { std::ptr::read(value) })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SliceDrain<'a, T> {}
+
+impl<'a, T> Drop for SliceDrain<'a, T> {
+    fn drop(&mut self) {
+        let remaining = std::mem::take(&mut self.iter).into_slice();
+        unsafe { std::ptr::drop_in_place(remaining) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Array, tests::strings};
+    use ::rayon::iter::{
+        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+    };
+
+    #[test]
+    fn parallel_sum_matches_sequential() {
+        let arr: Array<u64> = (0..10_000).collect();
+        let sequential: u64 = arr.iter().sum();
+        assert_eq!(arr.par_iter().sum::<u64>(), sequential);
+        assert_eq!((&arr).into_par_iter().sum::<u64>(), sequential);
+        assert_eq!(arr.into_par_iter().sum::<u64>(), sequential);
+    }
+
+    #[test]
+    fn into_par_iter_moves_elements_in_order() {
+        let collected: Vec<String> = strings(0..1000).into_par_iter().collect();
+        assert_eq!(collected, Vec::from(strings(0..1000)));
+    }
+
+    #[test]
+    fn into_par_iter_drops_unconsumed() {
+        let taken: Vec<String> = strings(0..1000).into_par_iter().take(10).collect();
+        assert_eq!(taken, Vec::from(strings(0..10)));
+    }
+
+    #[test]
+    fn par_iter_mut() {
+        let mut arr: Array<u64> = (0..1000).collect();
+        (&mut arr).into_par_iter().for_each(|n| *n *= 2);
+        assert!(arr.iter().enumerate().all(|(i, &n)| n == i as u64 * 2));
+    }
+}
@@ -0,0 +1,68 @@
+use super::Array;
+use ::serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{SeqAccess, Visitor},
+};
+use std::{alloc::Allocator, fmt, marker::PhantomData};
+
+impl<T: Serialize, A: Allocator> Serialize for Array<T, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Array<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArrayVisitor<T>(PhantomData<T>);
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for ArrayVisitor<T> {
+            type Value = Array<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+            fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+                // Don't trust the size hint blindly, it may come from untrusted input.
+                const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+                let max_prealloc = MAX_PREALLOC_BYTES / size_of::<T>().max(1);
+                let cap = seq.size_hint().unwrap_or(0).min(max_prealloc);
+                let mut arr = Array::with_capacity(cap);
+                while let Some(value) = seq.next_element()? {
+                    arr.push(value);
+                }
+                Ok(arr)
+            }
+        }
+        deserializer.deserialize_seq(ArrayVisitor(PhantomData))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::super::{Array, tests::strings};
+
+    #[test]
+    fn json_round_trip() {
+        let arr = strings(0..5);
+        let json = serde_json::to_string(&arr).unwrap();
+        assert_eq!(json, r#"["0","1","2","3","4"]"#);
+        let back: Array<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, arr);
+        let empty: Array<u32> = serde_json::from_str("[]").unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let arr: Array<u64> = (0..1000).collect();
+        let bytes = bincode::serialize(&arr).unwrap();
+        let back: Array<u64> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(back, arr);
+    }
+
+    #[test]
+    fn huge_length_prefix_is_not_preallocated() {
+        // Claims u64::MAX elements, but contains none.
+        let bytes = u64::MAX.to_le_bytes();
+        assert!(bincode::deserialize::<Array<u64>>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use super::{Array, drain::Drain};
+use std::{alloc::Allocator, ops::RangeBounds};
+
+impl<T, A: Allocator> Array<T, A> {
+    /// Replaces the elements in `range` with the elements of `replace_with`, returning an
+    /// iterator over the removed elements.
+    /// The replacement happens when the returned [Splice] is dropped, even if it was not fully
+    /// consumed.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, T, A>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+}
+
+pub struct Splice<'d, I: Iterator<Item = T>, T, A: Allocator> {
+    drain: Drain<'d, T, A>,
+    replace_with: I,
+}
+
+impl<'d, T, A: Allocator> Drain<'d, T, A> {
+    /// Writes elements from `replace_with` into the hole left by the drained range, returning
+    /// true if the hole was filled completely.
+    fn fill(&mut self, replace_with: &mut impl Iterator<Item = T>) -> bool {
+        while self.arr.len() < self.hole_end {
+            let Some(value) = replace_with.next() else {
+                return false;
+            };
+            unsafe { self.arr.push_within_capacity_unchecked(value) };
+        }
+        true
+    }
+    /// Moves the tail back by `additional` elements, growing the hole.
+    fn move_tail(&mut self, additional: usize) {
+        // Room is needed for the rest of the hole and the tail, past the written elements.
+        let used = self.full_len - self.arr.len();
+        self.arr.reserve(used + additional);
+        let tail = self.full_len - self.hole_end;
+        unsafe {
+            let from = self.arr.idx_to_ptr(self.hole_end);
+            from.copy_to(from.add(additional), tail);
+        }
+        self.hole_end += additional;
+        self.full_len += additional;
+    }
+}
+
+impl<'d, I: Iterator<Item = T>, T, A: Allocator> Iterator for Splice<'d, I, T, A> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'d, I: Iterator<Item = T>, T, A: Allocator> DoubleEndedIterator for Splice<'d, I, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<'d, I: Iterator<Item = T>, T, A: Allocator> ExactSizeIterator for Splice<'d, I, T, A> {}
+
+impl<'d, I: Iterator<Item = T>, T, A: Allocator> Drop for Splice<'d, I, T, A> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+        if self.drain.hole_end == self.drain.full_len {
+            // There is no tail to move out of the way.
+            self.drain.arr.extend(self.replace_with.by_ref());
+            return;
+        }
+        if !self.drain.fill(&mut self.replace_with) {
+            return;
+        }
+        // Guess how much more room is needed from the size hint first, to avoid buffering the
+        // rest of the replacement.
+        let (lower, _) = self.replace_with.size_hint();
+        if lower > 0 {
+            self.drain.move_tail(lower);
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+        }
+        let collected: Array<T> = self.replace_with.by_ref().collect();
+        if !collected.is_empty() {
+            self.drain.move_tail(collected.len());
+            self.drain.fill(&mut collected.into_iter());
+        }
+        // Drain's Drop moves the tail back to the end of the written elements.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::strings;
+
+    /// Hides the size hint, forcing the replacement to be collected.
+    struct NoHint<I>(I);
+    impl<I: Iterator> Iterator for NoHint<I> {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<I::Item> {
+            self.0.next()
+        }
+    }
+
+    fn check(range: std::ops::Range<usize>, replacement: std::ops::Range<usize>, hint: bool) {
+        let mut arr = strings(0..8);
+        let mut vec = Vec::from(strings(0..8));
+        let replace_with = strings(replacement.clone()).into_iter();
+        let removed: Vec<_> = if hint {
+            arr.splice(range.clone(), replace_with).collect()
+        } else {
+            arr.splice(range.clone(), NoHint(replace_with)).collect()
+        };
+        let expected: Vec<_> = vec.splice(range, strings(replacement)).collect();
+        assert_eq!(removed, expected);
+        assert_eq!(arr[..], vec[..]);
+    }
+
+    #[test]
+    fn longer_replacement() {
+        check(2..4, 10..15, true);
+        check(2..4, 10..15, false);
+        check(0..0, 10..13, true);
+        check(0..0, 10..13, false);
+    }
+
+    #[test]
+    fn shorter_replacement() {
+        check(2..6, 10..12, true);
+        check(2..6, 10..12, false);
+        check(1..7, 10..10, true);
+    }
+
+    #[test]
+    fn equal_replacement() {
+        check(2..5, 10..13, true);
+        check(2..5, 10..13, false);
+    }
+
+    #[test]
+    fn replacement_at_end() {
+        check(5..8, 10..16, true);
+        check(8..8, 10..12, false);
+    }
+
+    #[test]
+    fn unconsumed_splice_still_replaces() {
+        let mut arr = strings(0..6);
+        drop(arr.splice(1..3, strings(10..14)));
+        assert_eq!(arr[..], ["0", "10", "11", "12", "13", "3", "4", "5"]);
+    }
+}
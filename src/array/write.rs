@@ -0,0 +1,66 @@
+use super::Array;
+use std::{alloc::Allocator, fmt, io, str::Utf8Error};
+
+impl<A: Allocator> Array<u8, A> {
+    /// Returns the contents of the array as a string slice, if they are valid UTF-8.
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(self)
+    }
+}
+
+/// Appends the written bytes to the end of the array, never failing.
+impl<A: Allocator> io::Write for Array<u8, A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Appends the UTF-8 bytes of the written string to the end of the array.
+impl<A: Allocator> fmt::Write for Array<u8, A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Array;
+    use std::io;
+
+    #[test]
+    fn io_write() {
+        use std::io::Write;
+        let mut arr = Array::<u8>::new();
+        let id = "id";
+        write!(arr, "{id}-{:03}", 7).unwrap();
+        arr.write_all(b"!").unwrap();
+        assert_eq!(arr.write(b"ab").unwrap(), 2);
+        arr.flush().unwrap();
+        assert_eq!(arr[..], *b"id-007!ab");
+        let copied = io::copy(&mut &b"tail"[..], &mut arr).unwrap();
+        assert_eq!(copied, 4);
+        assert_eq!(arr[..], *b"id-007!abtail");
+    }
+
+    #[test]
+    fn fmt_write_utf8() {
+        use std::fmt::Write;
+        let mut arr = Array::<u8>::new();
+        let (accent, word) = ('é', "日本");
+        write!(arr, "{accent}ß{word}").unwrap();
+        arr.write_char('🦀').unwrap();
+        assert_eq!(arr.as_str(), Ok("éß日本🦀"));
+        assert_eq!(arr.len(), "éß日本🦀".len());
+        arr.pop();
+        assert!(arr.as_str().is_err());
+    }
+}
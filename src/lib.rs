@@ -1,2 +1,5 @@
 #![feature(allocator_api)]
+#![feature(iter_advance_by)]
 pub mod array;
+pub mod ring;
+pub mod stable;
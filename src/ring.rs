@@ -0,0 +1,469 @@
+use crate::array::Array;
+use std::{
+    alloc::{Allocator, Global},
+    fmt::Debug,
+    iter::FusedIterator,
+    ops::{Index, IndexMut},
+};
+
+/// A double-ended queue stored in a growable ring buffer.
+/// The elements live in the spare capacity of an [Array], whose length is always kept at 0, so
+/// the array only manages the allocation.
+pub struct RingBuffer<T, A: Allocator = Global> {
+    buf: Array<T, A>,
+    /// The physical index of the first element.
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub const fn new() -> Self {
+        RingBuffer::new_in(Global)
+    }
+    /// Creates an empty ring buffer with room for at least `cap` elements.
+    pub fn with_capacity(cap: usize) -> Self {
+        RingBuffer::with_capacity_in(cap, Global)
+    }
+}
+
+impl<T, A: Allocator> RingBuffer<T, A> {
+    pub const fn new_in(alloc: A) -> Self {
+        RingBuffer {
+            buf: Array::new_in(alloc),
+            head: 0,
+            len: 0,
+        }
+    }
+    /// Creates an empty ring buffer with room for at least `cap` elements in the provided
+    /// allocator.
+    pub fn with_capacity_in(cap: usize, alloc: A) -> Self {
+        RingBuffer {
+            buf: Array::with_capacity_in(cap, alloc),
+            head: 0,
+            len: 0,
+        }
+    }
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub const fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+    pub const fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+    /// Maps a logical index to its physical index in the buffer.
+    /// Requires that `idx <= self.capacity()`, where `idx == self.capacity()` maps back to the
+    /// head, as used by [Self::pop_front] to advance it.
+    const fn physical_idx(&self, idx: usize) -> usize {
+        // Written to avoid overflowing for zero-sized types, where the capacity is usize::MAX.
+        let until_end = self.capacity() - self.head;
+        if idx < until_end {
+            self.head + idx
+        } else {
+            idx - until_end
+        }
+    }
+    fn slot(&self, idx: usize) -> *mut T {
+        // The array's length is always 0, so the entire buffer is spare capacity.
+        unsafe { self.buf.as_ptr().cast_mut().add(self.physical_idx(idx)) }
+    }
+    /// Doubles the capacity, keeping the elements in order across the wrap.
+    fn grow(&mut self) {
+        let old_cap = self.capacity();
+        // Only zero-sized types can reach a capacity of usize::MAX, and they are then full.
+        let Some(additional) = old_cap.checked_add(1) else {
+            panic!("capacity overflow");
+        };
+        self.buf.reserve(additional);
+        let new_cap = self.capacity();
+        let head_len = old_cap - self.head;
+        if head_len >= self.len {
+            // The elements did not wrap around.
+            return;
+        }
+        // Move the part at the end of the old buffer to the end of the new one.
+        let new_head = new_cap - head_len;
+        unsafe {
+            let base = self.buf.as_mut_ptr();
+            base.add(self.head).copy_to(base.add(new_head), head_len);
+        }
+        self.head = new_head;
+    }
+    /// Appends `value` to the back of the buffer.
+    pub fn push_back(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+        unsafe { self.slot(self.len).write(value) };
+        self.len += 1;
+    }
+    /// Prepends `value` to the front of the buffer.
+    pub fn push_front(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+        self.head = self.physical_idx(self.capacity() - 1);
+        unsafe { self.slot(0).write(value) };
+        self.len += 1;
+    }
+    /// Removes the last element and returns it, or [None] if the buffer is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(self.slot(self.len).read()) }
+    }
+    /// Removes the first element and returns it, or [None] if the buffer is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = unsafe { self.slot(0).read() };
+        self.head = self.physical_idx(1);
+        self.len -= 1;
+        Some(value)
+    }
+    /// Returns a reference to the element at logical index `idx`, or [None] if it is out of
+    /// bounds.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        if idx >= self.len {
+            return None;
+        }
+        unsafe { Some(&*self.slot(idx)) }
+    }
+    /// Returns a mutable reference to the element at logical index `idx`, or [None] if it is out
+    /// of bounds.
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        if idx >= self.len {
+            return None;
+        }
+        unsafe { Some(&mut *self.slot(idx)) }
+    }
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+    /// Returns the elements as two slices, which together hold the elements in order.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let head_len = (self.capacity() - self.head).min(self.len);
+        unsafe {
+            (
+                core::slice::from_raw_parts(self.slot(0), head_len),
+                core::slice::from_raw_parts(self.buf.as_ptr(), self.len - head_len),
+            )
+        }
+    }
+    /// Drops all elements, keeping the allocation.
+    pub fn clear(&mut self) {
+        let head_len = (self.capacity() - self.head).min(self.len);
+        let front = core::ptr::slice_from_raw_parts_mut(self.slot(0), head_len);
+        let back = core::ptr::slice_from_raw_parts_mut(self.slot(head_len), self.len - head_len);
+        // Update the length first, so a panicking Drop impl can't cause a double drop.
+        self.len = 0;
+        self.head = 0;
+        unsafe {
+            front.drop_in_place();
+            back.drop_in_place();
+        }
+    }
+    /// Returns an iterator over the elements from front to back.
+    pub fn iter(&self) -> Iter<'_, T, A> {
+        Iter {
+            ring: self,
+            start: 0,
+            end: self.len,
+        }
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for RingBuffer<T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, A: Allocator> Index<usize> for RingBuffer<T, A> {
+    type Output = T;
+    #[track_caller]
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "Index {index} would be out of bounds for RingBuffer of length {len}",
+                len = self.len
+            ),
+        }
+    }
+}
+
+impl<T, A: Allocator> IndexMut<usize> for RingBuffer<T, A> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!("Index {index} would be out of bounds for RingBuffer of length {len}"),
+        }
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for RingBuffer<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T, A: Allocator + Default> FromIterator<T> for RingBuffer<T, A> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ring = RingBuffer::new_in(A::default());
+        ring.extend(iter);
+        ring
+    }
+}
+
+impl<T, A: Allocator + Default> Default for RingBuffer<T, A> {
+    fn default() -> Self {
+        RingBuffer::new_in(A::default())
+    }
+}
+
+impl<T, A: Allocator> Drop for RingBuffer<T, A> {
+    fn drop(&mut self) {
+        // The array only frees the buffer, as its length is 0.
+        self.clear();
+    }
+}
+
+/// An iterator over the elements of a [RingBuffer], from front to back.
+pub struct Iter<'r, T, A: Allocator> {
+    ring: &'r RingBuffer<T, A>,
+    start: usize,
+    end: usize,
+}
+
+impl<'r, T, A: Allocator> Iterator for Iter<'r, T, A> {
+    type Item = &'r T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        let value = unsafe { &*self.ring.slot(self.start) };
+        self.start += 1;
+        Some(value)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'r, T, A: Allocator> DoubleEndedIterator for Iter<'r, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        unsafe { Some(&*self.ring.slot(self.end)) }
+    }
+}
+
+impl<'r, T, A: Allocator> ExactSizeIterator for Iter<'r, T, A> {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<'r, T, A: Allocator> FusedIterator for Iter<'r, T, A> {}
+
+impl<'r, T, A: Allocator> IntoIterator for &'r RingBuffer<T, A> {
+    type IntoIter = Iter<'r, T, A>;
+    type Item = &'r T;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the elements of a [RingBuffer], from front to back.
+pub struct IntoIter<T, A: Allocator> {
+    ring: RingBuffer<T, A>,
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ring.pop_front()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ring.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ring.pop_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> IntoIterator for RingBuffer<T, A> {
+    type IntoIter = IntoIter<T, A>;
+    type Item = T;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { ring: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RingBuffer;
+    use std::{cell::Cell, collections::VecDeque, rc::Rc};
+
+    /// Returns a full ring buffer whose elements wrap past the physical end of its buffer, along
+    /// with the equivalent [VecDeque].
+    fn wrapped() -> (RingBuffer<usize>, VecDeque<usize>) {
+        let mut ring = RingBuffer::with_capacity(8);
+        let mut deque = VecDeque::new();
+        for n in 0..ring.capacity() {
+            ring.push_back(n);
+            deque.push_back(n);
+        }
+        for _ in 0..3 {
+            assert_eq!(ring.pop_front(), deque.pop_front());
+        }
+        for n in 100..103 {
+            ring.push_back(n);
+            deque.push_back(n);
+        }
+        (ring, deque)
+    }
+
+    fn assert_matches(ring: &RingBuffer<usize>, deque: &VecDeque<usize>) {
+        assert_eq!(ring.len(), deque.len());
+        assert!(ring.iter().eq(deque.iter()));
+        assert!(ring.iter().rev().eq(deque.iter().rev()));
+        let (front, back) = ring.as_slices();
+        let (deque_front, deque_back) = deque.as_slices();
+        assert_eq!([front, back].concat(), [deque_front, deque_back].concat());
+        assert_eq!(ring.front(), deque.front());
+        assert_eq!(ring.back(), deque.back());
+    }
+
+    #[test]
+    fn wrap_past_end() {
+        let (ring, deque) = wrapped();
+        assert_eq!(ring.len(), ring.capacity());
+        let (front, back) = ring.as_slices();
+        assert!(!front.is_empty() && back == [100, 101, 102]);
+        assert_matches(&ring, &deque);
+        for idx in 0..ring.len() {
+            assert_eq!(ring[idx], deque[idx]);
+        }
+    }
+
+    #[test]
+    fn grow_while_wrapped() {
+        let (mut ring, mut deque) = wrapped();
+        let cap = ring.capacity();
+        ring.push_back(200);
+        deque.push_back(200);
+        assert!(ring.capacity() > cap);
+        assert_matches(&ring, &deque);
+
+        let (mut ring, mut deque) = wrapped();
+        ring.push_front(200);
+        deque.push_front(200);
+        assert!(ring.capacity() > cap);
+        assert_matches(&ring, &deque);
+    }
+
+    #[test]
+    fn mixed_operations_match_vec_deque() {
+        let mut ring = RingBuffer::new();
+        let mut deque = VecDeque::new();
+        for n in 0..200 {
+            match n % 7 {
+                0 | 3 => ring.push_front(n),
+                1 | 4 | 5 => ring.push_back(n),
+                2 => assert_eq!(ring.pop_front(), deque.pop_front()),
+                _ => assert_eq!(ring.pop_back(), deque.pop_back()),
+            }
+            match n % 7 {
+                0 | 3 => deque.push_front(n),
+                1 | 4 | 5 => deque.push_back(n),
+                _ => {}
+            }
+            assert_matches(&ring, &deque);
+        }
+        assert!(ring.into_iter().eq(deque));
+    }
+
+    #[test]
+    fn zero_sized() {
+        let mut ring = RingBuffer::new();
+        assert_eq!(ring.capacity(), usize::MAX);
+        ring.push_front(());
+        ring.push_back(());
+        ring.push_front(());
+        assert_eq!(ring.len(), 3);
+        let (front, back) = ring.as_slices();
+        assert_eq!(front.len() + back.len(), 3);
+        assert_eq!(ring.iter().count(), 3);
+        assert_eq!(ring.pop_back(), Some(()));
+        assert_eq!(ring.pop_front(), Some(()));
+        assert_eq!(ring.pop_front(), Some(()));
+        assert_eq!(ring.pop_front(), None);
+    }
+
+    #[test]
+    fn wrapped_elements_are_dropped_once() {
+        struct Tracked(Rc<Cell<usize>>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let drops = Rc::new(Cell::new(0));
+        let mut ring = RingBuffer::with_capacity(4);
+        let cap = ring.capacity();
+        for _ in 0..cap {
+            ring.push_back(Tracked(drops.clone()));
+        }
+        drop(ring.pop_front());
+        drop(ring.pop_front());
+        ring.push_back(Tracked(drops.clone()));
+        ring.push_back(Tracked(drops.clone()));
+        assert_eq!(drops.get(), 2);
+        ring.clear();
+        assert_eq!(drops.get(), cap + 2);
+        assert!(ring.is_empty());
+        ring.push_front(Tracked(drops.clone()));
+        ring.push_front(Tracked(drops.clone()));
+        drop(ring);
+        assert_eq!(drops.get(), cap + 4);
+    }
+
+    #[test]
+    fn zst_push_past_max_len_panics() {
+        let mut ring = RingBuffer::<()>::new();
+        ring.len = usize::MAX;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| ring.push_back(())));
+        let message = *result.unwrap_err().downcast::<&str>().unwrap();
+        assert_eq!(message, "capacity overflow");
+        assert_eq!(ring.len(), usize::MAX);
+        // Avoid dropping usize::MAX elements one by one.
+        ring.len = 0;
+    }
+}
@@ -0,0 +1,292 @@
+use crate::array::Array;
+use std::{
+    alloc::{Allocator, Global},
+    fmt::Debug,
+    iter::FusedIterator,
+    mem::MaybeUninit,
+};
+
+/// An array whose elements keep their indices when others are removed.
+/// Removing an element leaves a tombstone in its slot, tracked by an occupancy bitset, until
+/// [Self::tombstone_compact] reclaims the holes.
+/// Only the slots live in the allocator `A`. The occupancy bitset, one bit per slot, is always
+/// allocated in [Global], so that [Self::new_in] stays `const` and doesn't require `A: Clone`.
+pub struct StableArray<T, A: Allocator = Global> {
+    slots: Array<MaybeUninit<T>, A>,
+    /// One bit per slot, set if the slot holds an element.
+    /// Allocated in [Global] regardless of `A`.
+    occupied: Array<u64>,
+    /// The number of occupied slots.
+    len: usize,
+}
+
+impl<T> StableArray<T> {
+    pub const fn new() -> Self {
+        StableArray::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> StableArray<T, A> {
+    /// Creates an empty stable array whose slots are allocated in `alloc`.
+    /// The occupancy bitset is still allocated in [Global].
+    pub const fn new_in(alloc: A) -> Self {
+        StableArray {
+            slots: Array::new_in(alloc),
+            occupied: Array::new(),
+            len: 0,
+        }
+    }
+    /// Returns the number of elements, not counting tombstones.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the number of slots, including tombstones.
+    /// This is the index the next pushed element will receive.
+    pub const fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+    pub const fn allocator(&self) -> &A {
+        self.slots.allocator()
+    }
+    fn is_occupied(&self, idx: usize) -> bool {
+        self.occupied
+            .get(idx / 64)
+            .is_some_and(|word| word & (1 << (idx % 64)) != 0)
+    }
+    /// Appends `value` in a new slot, returning its index.
+    pub fn push(&mut self, value: T) -> usize {
+        let idx = self.slots.len();
+        if idx.is_multiple_of(64) {
+            self.occupied.push(0);
+        }
+        self.slots.push(MaybeUninit::new(value));
+        self.occupied[idx / 64] |= 1 << (idx % 64);
+        self.len += 1;
+        idx
+    }
+    /// Returns a reference to the element at `idx`, or [None] if it is out of bounds or has been
+    /// removed.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        if !self.is_occupied(idx) {
+            return None;
+        }
+        unsafe { Some(self.slots[idx].assume_init_ref()) }
+    }
+    /// Returns a mutable reference to the element at `idx`, or [None] if it is out of bounds or
+    /// has been removed.
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        if !self.is_occupied(idx) {
+            return None;
+        }
+        unsafe { Some(self.slots[idx].assume_init_mut()) }
+    }
+    /// Removes the element at `idx`, leaving a tombstone so the other elements keep their
+    /// indices.
+    /// Returns [None] if `idx` is out of bounds or was already removed.
+    pub fn tombstone_remove(&mut self, idx: usize) -> Option<T> {
+        if !self.is_occupied(idx) {
+            return None;
+        }
+        self.occupied[idx / 64] &= !(1 << (idx % 64));
+        self.len -= 1;
+        unsafe { Some(self.slots[idx].assume_init_read()) }
+    }
+    /// Moves all elements down into the tombstoned slots, preserving their order.
+    /// This invalidates the indices of every element after the first tombstone.
+    pub fn tombstone_compact(&mut self) {
+        let mut write = 0;
+        for read in 0..self.slots.len() {
+            if !self.is_occupied(read) {
+                continue;
+            }
+            if read != write {
+                unsafe {
+                    let base = self.slots.as_mut_ptr();
+                    base.add(read).copy_to_nonoverlapping(base.add(write), 1);
+                }
+            }
+            write += 1;
+        }
+        // Every slot below `len` is occupied now.
+        unsafe { self.slots.set_len(self.len) };
+        self.occupied.clear();
+        self.occupied.resize(self.len.div_ceil(64), u64::MAX);
+        if !self.len.is_multiple_of(64) {
+            self.occupied[self.len / 64] = (1 << (self.len % 64)) - 1;
+        }
+    }
+    /// Drops all elements and tombstones.
+    pub fn clear(&mut self) {
+        let len = self.slots.len();
+        // Forget the slots first, so a panicking Drop impl can't cause a double drop.
+        unsafe { self.slots.set_len(0) };
+        let occupied = core::mem::take(&mut self.occupied);
+        self.len = 0;
+        let base = self.slots.as_mut_ptr();
+        for idx in 0..len {
+            if occupied[idx / 64] & (1 << (idx % 64)) != 0 {
+                unsafe { base.add(idx).cast::<T>().drop_in_place() };
+            }
+        }
+    }
+    /// Returns an iterator over the elements and their indices, skipping tombstones.
+    pub fn iter(&self) -> Iter<'_, T, A> {
+        Iter {
+            arr: self,
+            idx: 0,
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T: Debug, A: Allocator> Debug for StableArray<T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T, A: Allocator + Default> Default for StableArray<T, A> {
+    fn default() -> Self {
+        StableArray::new_in(A::default())
+    }
+}
+
+impl<T, A: Allocator> Drop for StableArray<T, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// An iterator over the elements of a [StableArray] and their indices, skipping tombstones.
+pub struct Iter<'s, T, A: Allocator> {
+    arr: &'s StableArray<T, A>,
+    idx: usize,
+    remaining: usize,
+}
+
+impl<'s, T, A: Allocator> Iterator for Iter<'s, T, A> {
+    type Item = (usize, &'s T);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            let idx = self.idx;
+            self.idx += 1;
+            if let Some(value) = self.arr.get(idx) {
+                self.remaining -= 1;
+                return Some((idx, value));
+            }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'s, T, A: Allocator> ExactSizeIterator for Iter<'s, T, A> {}
+
+impl<'s, T, A: Allocator> FusedIterator for Iter<'s, T, A> {}
+
+impl<'s, T, A: Allocator> IntoIterator for &'s StableArray<T, A> {
+    type IntoIter = Iter<'s, T, A>;
+    type Item = (usize, &'s T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StableArray;
+    use std::{cell::Cell, rc::Rc};
+
+    /// Returns a stable array holding the strings of `0..count`, at their own indices.
+    fn strings(count: usize) -> StableArray<String> {
+        let mut arr = StableArray::new();
+        for n in 0..count {
+            assert_eq!(arr.push(n.to_string()), n);
+        }
+        arr
+    }
+
+    #[test]
+    fn indices_survive_removal() {
+        let mut arr = strings(150);
+        for idx in (0..150).filter(|idx| idx % 3 == 0) {
+            assert_eq!(arr.tombstone_remove(idx), Some(idx.to_string()));
+        }
+        assert_eq!(arr.tombstone_remove(63), None);
+        assert_eq!(arr.len(), 100);
+        assert_eq!(arr.slot_count(), 150);
+        for idx in 0..150 {
+            let expected = (idx % 3 != 0).then(|| idx.to_string());
+            assert_eq!(arr.get(idx), expected.as_ref());
+        }
+        assert_eq!(arr.get(150), None);
+        // New elements are pushed after the tombstones.
+        assert_eq!(arr.push("new".to_string()), 150);
+        *arr.get_mut(64).unwrap() += "!";
+        assert_eq!(arr.get(64).map(String::as_str), Some("64!"));
+    }
+
+    #[test]
+    fn compact_across_word_boundary() {
+        let mut arr = strings(150);
+        for idx in (0..150).filter(|idx| idx % 2 == 0) {
+            arr.tombstone_remove(idx);
+        }
+        arr.tombstone_compact();
+        // 75 remaining elements span two bitset words, the second one partially.
+        assert_eq!(arr.len(), 75);
+        assert_eq!(arr.slot_count(), 75);
+        for idx in 0..75 {
+            assert_eq!(arr.get(idx), Some(&(idx * 2 + 1).to_string()));
+        }
+        assert_eq!(arr.get(75), None);
+        assert!(arr.iter().map(|(idx, _)| idx).eq(0..75));
+        assert_eq!(arr.push("new".to_string()), 75);
+        assert_eq!(arr.get(75).map(String::as_str), Some("new"));
+        arr.tombstone_remove(70);
+        arr.tombstone_compact();
+        assert_eq!(arr.len(), 75);
+        assert_eq!(arr.get(70), Some(&"143".to_string()));
+        assert_eq!(arr.get(74).map(String::as_str), Some("new"));
+    }
+
+    #[test]
+    fn compact_exact_words() {
+        let mut arr = strings(130);
+        arr.tombstone_remove(0);
+        arr.tombstone_remove(129);
+        arr.tombstone_compact();
+        assert_eq!(arr.len(), 128);
+        assert!(arr.iter().map(|(idx, _)| idx).eq(0..128));
+        assert_eq!(arr.push("new".to_string()), 128);
+        assert_eq!(arr.get(128).map(String::as_str), Some("new"));
+    }
+
+    #[test]
+    fn tombstones_are_not_dropped_twice() {
+        struct Tracked(Rc<Cell<usize>>);
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let drops = Rc::new(Cell::new(0));
+        let mut arr = StableArray::new();
+        for _ in 0..100 {
+            arr.push(Tracked(drops.clone()));
+        }
+        for idx in 60..70 {
+            drop(arr.tombstone_remove(idx));
+        }
+        assert_eq!(drops.get(), 10);
+        arr.tombstone_compact();
+        assert_eq!(drops.get(), 10);
+        drop(arr);
+        assert_eq!(drops.get(), 100);
+    }
+}